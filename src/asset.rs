@@ -0,0 +1,140 @@
+use crate::FamilyCashFlow;
+
+// NISA の年間投資枠と生涯投資枠（つみたて投資枠 + 成長投資枠）
+// https://www.fsa.go.jp/policy/nisa2/about/index.html
+const NISA_ANNUAL_LIMIT: u32 = 3600000;
+const NISA_LIFETIME_LIMIT: u64 = 18000000;
+
+// NISA, iDeCo などの投資口座
+// 掛金の所得控除や運用益の税金は考慮しない
+#[derive(Debug, Clone)]
+pub struct InvestmentAccount {
+    pub initial_balance: u32,
+    pub annual_contribution: u32, // 毎年の積立額（現金から移す）
+    pub contribution_end_year: u16, // not include
+    pub expected_yield: f64, // 期待利回り
+    pub withdrawable_year: u16, // この年から取り崩せる（iDeCo は 60 歳になる年）
+}
+
+#[derive(Debug, Clone)]
+pub struct Savings {
+    pub initial_cash: u32,
+    pub cash_interest_rate: f64, // 預金の税引後の利率
+    pub nisa: Option<InvestmentAccount>,
+    pub ideco: Option<InvestmentAccount>,
+}
+
+// 年末時点の資産
+#[derive(Debug)]
+pub struct YearlyAssets {
+    pub year: u16,
+    pub net_cash_flow: i64, // その年の収支
+    pub cash: i64,
+    pub nisa: i64,
+    pub ideco: i64,
+}
+
+impl YearlyAssets {
+    pub fn total(&self) -> i64 {
+        self.cash + self.nisa + self.ideco
+    }
+}
+
+#[derive(Debug)]
+pub struct AssetSimulation {
+    pub yearly_assets: Vec<YearlyAssets>,
+    pub first_negative_year: Option<u16>, // 取り崩せる資産を使っても現金が足りなくなる最初の年
+}
+
+struct AccountState<'a> {
+    account: Option<&'a InvestmentAccount>,
+    balance: f64,
+    contributed: u64, // 積み立てた元本の累計
+}
+
+impl<'a> AccountState<'a> {
+    fn new(account: Option<&'a InvestmentAccount>) -> Self {
+        Self {
+            account,
+            balance: account.map(|a| a.initial_balance as f64).unwrap_or(0.0),
+            contributed: account.map(|a| a.initial_balance as u64).unwrap_or(0),
+        }
+    }
+
+    // 運用益を加えて、その年の積立額を返す（available は積立に回せる現金）
+    fn grow(&mut self, year: u16, annual_limit: u32, lifetime_limit: u64, available: u32) -> u32 {
+        let Some(account) = self.account else {
+            return 0;
+        };
+        self.balance *= 1.0 + account.expected_yield;
+        if account.contribution_end_year <= year {
+            return 0;
+        }
+        let contribution = account.annual_contribution
+            .min(annual_limit)
+            .min(lifetime_limit.saturating_sub(self.contributed) as u32)
+            .min(available);
+        self.balance += contribution as f64;
+        self.contributed += contribution as u64;
+        contribution
+    }
+
+    // 足りない分を取り崩して、取り崩した額を返す
+    fn withdraw(&mut self, year: u16, shortfall: f64) -> f64 {
+        match self.account {
+            Some(account) if account.withdrawable_year <= year => {
+                let amount = shortfall.min(self.balance).max(0.0);
+                self.balance -= amount;
+                amount
+            },
+            _ => 0.0,
+        }
+    }
+}
+
+pub fn simulate_assets(cash_flows: &[FamilyCashFlow], savings: &Savings) -> AssetSimulation {
+    let mut cash = savings.initial_cash as f64;
+    let mut nisa = AccountState::new(savings.nisa.as_ref());
+    let mut ideco = AccountState::new(savings.ideco.as_ref());
+
+    let mut yearly_assets = Vec::new();
+    let mut first_negative_year = None;
+
+    for cash_flow in cash_flows {
+        let year = cash_flow.year;
+
+        // 預金の利息（マイナスの時はつかない）
+        if 0.0 < cash {
+            cash *= 1.0 + savings.cash_interest_rate;
+        }
+        cash += cash_flow.balance as f64;
+
+        // 積立（現金が足りない年は手元の現金の分まで）
+        cash -= nisa.grow(year, NISA_ANNUAL_LIMIT, NISA_LIFETIME_LIMIT, cash.max(0.0) as u32) as f64;
+        cash -= ideco.grow(year, u32::MAX, u64::MAX, cash.max(0.0) as u32) as f64;
+
+        // 現金が足りない時は NISA、iDeCo の順に取り崩す
+        if cash < 0.0 {
+            cash += nisa.withdraw(year, -cash);
+        }
+        if cash < 0.0 {
+            cash += ideco.withdraw(year, -cash);
+        }
+        if cash < 0.0 && first_negative_year.is_none() {
+            first_negative_year = Some(year);
+        }
+
+        yearly_assets.push(YearlyAssets {
+            year,
+            net_cash_flow: cash_flow.balance,
+            cash: cash as i64,
+            nisa: nisa.balance as i64,
+            ideco: ideco.balance as i64,
+        });
+    }
+
+    AssetSimulation {
+        yearly_assets,
+        first_negative_year,
+    }
+}
@@ -0,0 +1,127 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ALLOWANCES, ANNUAL_LIVING_ALONE_EXPENSE, BASE_FOOD_EXPENSE, BASE_FUEL_LIGHT_WATER_GAS_ETC_EXPENSE, BASE_FURNITURE_EXPENSE,
+    CHILD_MARRIAGE_SUPPORT_EXPENSE, CLOTHING_EXPENSE, CLOTHING_RATES, DIFF_FROM_PARENT_AGE,
+    DRIVER_LICENCE_AQUISITION_AGE, DRIVER_LICENCE_AQUISITION_EXPENSE, EXTRA_EDUCATION_EXPENSES,
+    EXTRACURRICULAR_ACTIVITIES_EXPENSES, FOOD_RATES, INITIAL_LIVING_ALONE_EXPENSE, LEISURE_EXPENSES, LIFESPAN_YEARS,
+    LIVING_ALONE_CHILD_ALLOWANCE, LifeStageAges, MARRIAGE_AGE, MEDICAL_EXPENSES, MOBILE_EXPENSE, MOBILE_START_AGE,
+    PARENT_FUNERAL_EXPENSE, PERSON_FOOD_EXPENSE, PERSON_FUEL_LIGHT_WATER_GAS_ETC_EXPENSE, PERSON_FURNITURE_EXPENSE,
+    WORKING_CHILD_ALLOWANCE,
+};
+use crate::daycare::DaycareFeeTable;
+use crate::inflation::Inflation;
+
+// 見積もりの前提となる値
+// 既定値は lib.rs の定数（2022 〜 2023 年の調査に基づく値）
+// 設定ファイルで一部だけ指定した場合、残りは既定値になる
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct Assumptions {
+    pub lifespan_years: u8, // 寿命
+    pub marriage_age: u8, // 子供の結婚年齢
+    pub diff_from_parent_age: u8, // 親との年齢差
+    pub driver_licence_aquisition_age: u8, // 免許取得年齢
+    pub mobile_start_age: u8, // 携帯電話を持つ年齢
+
+    // 幼稚園から大学院までの状態が始まる年齢（大学、大学院の年数は EducationPlan でも使う）
+    pub life_stage_ages: LifeStageAges,
+
+    pub initial_living_alone_expense: u32, // 一人暮らしの契約、引越し
+    pub annual_living_alone_expense: u32, // 一人暮らしの家賃、仕送り、更新料の年割
+    pub person_food_expense: u32,
+    pub base_food_expense: u32,
+    pub person_fuel_light_water_gas_etc_expense: u32,
+    pub base_fuel_light_water_gas_etc_expense: u32,
+    pub person_furniture_expense: u32,
+    pub base_furniture_expense: u32,
+    pub clothing_expense: u32,
+    pub mobile_expense: u32,
+    pub child_marriage_support_expense: u32,
+    pub parent_funeral_expense: u32,
+    pub driver_licence_aquisition_expense: u32,
+    pub living_alone_child_allowance: u32, // 一人暮らしの子供へのお小遣い、プレゼント
+    pub working_child_allowance: u32, // 働いている子供へのお小遣い、プレゼント
+
+    // (この年齢から, clothing_expense に対する比率) を年齢の昇順に並べたもの
+    pub clothing_rates: Vec<(u8, f64)>,
+
+    // (この年齢から, person_food_expense に対する比率) を年齢の昇順に並べたもの
+    pub food_rates: Vec<(u8, f64)>,
+
+    // (この年齢から, 年額) を年齢の昇順に並べたもの
+    pub medical_expenses: Vec<(u8, u32)>,
+    pub extra_education_expenses: Vec<(u8, u32)>, // 塾、予備校
+    pub extracurricular_activities_expenses: Vec<(u8, u32)>, // 習い事
+    pub allowances: Vec<(u8, u32)>, // お小遣い、プレゼント
+    pub leisure_expenses: Vec<(u8, u32)>, // レジャー、旅行
+
+    pub inflation: Inflation,
+
+    // 住んでいる区の保育料
+    pub daycare: DaycareFeeTable,
+}
+
+impl Default for Assumptions {
+    fn default() -> Self {
+        Self {
+            lifespan_years: LIFESPAN_YEARS,
+            marriage_age: MARRIAGE_AGE,
+            diff_from_parent_age: DIFF_FROM_PARENT_AGE,
+            driver_licence_aquisition_age: DRIVER_LICENCE_AQUISITION_AGE,
+            mobile_start_age: MOBILE_START_AGE,
+            life_stage_ages: LifeStageAges::default(),
+            initial_living_alone_expense: INITIAL_LIVING_ALONE_EXPENSE,
+            annual_living_alone_expense: ANNUAL_LIVING_ALONE_EXPENSE,
+            person_food_expense: PERSON_FOOD_EXPENSE,
+            base_food_expense: BASE_FOOD_EXPENSE,
+            person_fuel_light_water_gas_etc_expense: PERSON_FUEL_LIGHT_WATER_GAS_ETC_EXPENSE,
+            base_fuel_light_water_gas_etc_expense: BASE_FUEL_LIGHT_WATER_GAS_ETC_EXPENSE,
+            person_furniture_expense: PERSON_FURNITURE_EXPENSE,
+            base_furniture_expense: BASE_FURNITURE_EXPENSE,
+            clothing_expense: CLOTHING_EXPENSE,
+            mobile_expense: MOBILE_EXPENSE,
+            child_marriage_support_expense: CHILD_MARRIAGE_SUPPORT_EXPENSE,
+            parent_funeral_expense: PARENT_FUNERAL_EXPENSE,
+            driver_licence_aquisition_expense: DRIVER_LICENCE_AQUISITION_EXPENSE,
+            living_alone_child_allowance: LIVING_ALONE_CHILD_ALLOWANCE,
+            working_child_allowance: WORKING_CHILD_ALLOWANCE,
+            clothing_rates: CLOTHING_RATES.to_vec(),
+            food_rates: FOOD_RATES.to_vec(),
+            medical_expenses: MEDICAL_EXPENSES.to_vec(),
+            extra_education_expenses: EXTRA_EDUCATION_EXPENSES.to_vec(),
+            extracurricular_activities_expenses: EXTRACURRICULAR_ACTIVITIES_EXPENSES.to_vec(),
+            allowances: ALLOWANCES.to_vec(),
+            leisure_expenses: LEISURE_EXPENSES.to_vec(),
+            inflation: Inflation::default(),
+            daycare: DaycareFeeTable::default(),
+        }
+    }
+}
+
+impl Assumptions {
+    #[cfg(feature = "serde")]
+    pub fn from_toml_str(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    // 親の葬式の年齢
+    pub fn parent_funeral_age(&self) -> u8 {
+        self.lifespan_years.saturating_sub(self.diff_from_parent_age)
+    }
+}
+
+// 年齢の昇順に並んだ (この年齢から, 値) の表を引く
+pub fn lookup_age_table<T: Copy + Default>(table: &[(u8, T)], age: u8) -> T {
+    table.iter()
+        .rev()
+        .find(|(from_age, _)| *from_age <= age)
+        .map(|(_, value)| *value)
+        .unwrap_or_default()
+}
@@ -0,0 +1,156 @@
+use chrono::Datelike;
+
+use crate::{LifeStage, Person};
+use crate::assumptions::Assumptions;
+use crate::education::{HigherEducation, SchoolType, UniversityType};
+use crate::tax::{HouseholdResidentTax, PersonTax};
+
+// 児童手当（2024 年 10 月からの制度、所得制限なし、高校生年代まで）
+// https://www.cfa.go.jp/policies/kokoseido/jidouteate/
+const CHILD_ALLOWANCE_UNDER_3: u32 = 15000 * 12;
+const CHILD_ALLOWANCE: u32 = 10000 * 12;
+const CHILD_ALLOWANCE_THIRD_CHILD: u32 = 30000 * 12;
+const CHILD_ALLOWANCE_LAST_AGE: u8 = 17;
+
+// 第 3 子以降の数え方で対象になる年齢（22 歳の年度末まで）
+const CHILD_ALLOWANCE_COUNTING_LAST_AGE: u8 = 21;
+
+// 東京都の 018 サポート（18 歳の年度末まで）
+// https://www.fukushihoken.metro.tokyo.lg.jp/shoushi/018support/
+const TOKYO_CHILD_SUPPORT: u32 = 5000 * 12;
+const TOKYO_CHILD_SUPPORT_START_YEAR: u16 = 2024;
+
+// 妊婦のための支援給付（出産育児一時金は出産費用から差し引く）
+// https://www.cfa.go.jp/policies/boshihoken/
+const PREGNANCY_SUPPORT: u32 = 100000;
+
+// 東京都の私立高校授業料の実質無償化（所得制限なし、平均授業料まで）
+// 公立の学費は文部科学省の調査で既に就学支援金を引いた額なので対象にしない
+// https://www.mext.go.jp/a_menu/shotou/mushouka/
+// https://www.tokyo-shigaku.or.jp/
+const PRIVATE_HIGH_SCHOOL_TUITION_SUPPORT: u32 = 483311;
+
+// 高等教育の修学支援新制度（住民税非課税世帯と、2025 年からの多子世帯）の授業料、入学金の減免の上限
+// https://www.mext.go.jp/kyufu/
+const MULTI_CHILD_HOUSEHOLD_START_YEAR: u16 = 2025;
+const MULTI_CHILD_HOUSEHOLD_NUM_CHILDREN: usize = 3;
+
+fn estimate_higher_education_support_limit(stage: &LifeStage, is_private: bool) -> Option<(u32, u32)> {
+    // (授業料, 入学金)
+    match (stage, is_private) {
+        (LifeStage::UnderGraduate, false) => Some((535800, 282000)),
+        (LifeStage::UnderGraduate, true) => Some((700000, 260000)),
+        (LifeStage::JuniorCollege, _) => Some((620000, 250000)),
+        (LifeStage::Vocational, _) => Some((590000, 160000)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PersonBenefits {
+    pub name: String,
+    pub child_allowance: u32, // 児童手当
+    pub tokyo_child_support: u32, // 018 サポート
+    pub childbirth_allowance: u32, // 妊婦のための支援給付
+    pub high_school_tuition_support: u32, // 高校の授業料の支援
+    pub higher_education_support: u32, // 大学、短大、専門学校の授業料、入学金の減免
+}
+
+impl PersonBenefits {
+    pub fn total(&self) -> u32 {
+        self.child_allowance +
+            self.tokyo_child_support +
+            self.childbirth_allowance +
+            self.high_school_tuition_support +
+            self.higher_education_support
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FamilyBenefits {
+    pub member_benefits: Vec<PersonBenefits>,
+}
+
+impl FamilyBenefits {
+    pub fn total(&self) -> u32 {
+        self.member_benefits.iter().map(|benefits| benefits.total()).sum()
+    }
+}
+
+// taxes はその年の tax::estimate_family_taxes（住民税非課税世帯の判定に使う）
+pub fn estimate_family_benefits(people: &[Person], year: u16, assumptions: &Assumptions, taxes: &[PersonTax]) -> FamilyBenefits {
+    // 生まれている子供を年齢の高い順に並べたもの
+    let mut children: Vec<(&Person, u8)> = people.iter()
+        .filter(|person| person.is_child)
+        .filter_map(|person| person.get_grade_age(year, assumptions).map(|age| (person, age)))
+        .collect();
+    children.sort_by_key(|(person, _)| person.birth_date);
+
+    // 4 月 2 日より後に生まれる子供は学年の年齢がないので出産の給付だけ
+    let newborns = people.iter()
+        .filter(|person| person.is_child && person.birth_date.year() == year as i32)
+        .filter(|person| person.get_grade_age(year, assumptions).is_none());
+
+    let num_dependent_children = children.iter()
+        .filter(|(person, age)| person.get_life_stage(*age, assumptions) != LifeStage::Working)
+        .count();
+    let is_multi_child_household = MULTI_CHILD_HOUSEHOLD_START_YEAR <= year && MULTI_CHILD_HOUSEHOLD_NUM_CHILDREN <= num_dependent_children;
+    let is_tax_exempt_household = HouseholdResidentTax::new(taxes) == HouseholdResidentTax::Exempt;
+
+    let mut counting_rank = 0;
+    let mut member_benefits = Vec::new();
+    for (person, age) in children {
+        let mut benefits = PersonBenefits {
+            name: person.name.clone(),
+            ..Default::default()
+        };
+
+        if age <= CHILD_ALLOWANCE_COUNTING_LAST_AGE {
+            counting_rank += 1;
+        }
+        if age <= CHILD_ALLOWANCE_LAST_AGE {
+            benefits.child_allowance = if 3 <= counting_rank {
+                CHILD_ALLOWANCE_THIRD_CHILD
+            } else if age < 3 {
+                CHILD_ALLOWANCE_UNDER_3
+            } else {
+                CHILD_ALLOWANCE
+            };
+            if TOKYO_CHILD_SUPPORT_START_YEAR <= year {
+                benefits.tokyo_child_support = TOKYO_CHILD_SUPPORT;
+            }
+        }
+
+        if person.birth_date.year() == year as i32 {
+            benefits.childbirth_allowance = PREGNANCY_SUPPORT;
+        }
+
+        let stage = person.get_life_stage(age, assumptions);
+        let plan = person.get_education_plan();
+        if stage == LifeStage::HighSchool && plan.high_school != SchoolType::Public {
+            benefits.high_school_tuition_support = PRIVATE_HIGH_SCHOOL_TUITION_SUPPORT;
+        }
+
+        if is_multi_child_household || is_tax_exempt_household {
+            let is_private = plan.university != UniversityType::National || plan.higher_education != HigherEducation::University;
+            if let Some((tuition, admission)) = estimate_higher_education_support_limit(&stage, is_private) {
+                benefits.higher_education_support = tuition;
+                let is_first_year = age.checked_sub(1).is_none_or(|prev_age| person.get_life_stage(prev_age, assumptions) != stage);
+                if is_first_year {
+                    benefits.higher_education_support += admission;
+                }
+            }
+        }
+
+        member_benefits.push(benefits);
+    }
+    for person in newborns {
+        member_benefits.push(PersonBenefits {
+            name: person.name.clone(),
+            childbirth_allowance: PREGNANCY_SUPPORT,
+            ..Default::default()
+        });
+    }
+
+    FamilyBenefits { member_benefits }
+}
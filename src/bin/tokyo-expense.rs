@@ -0,0 +1,197 @@
+use std::process::ExitCode;
+
+use tokyo_lifetime_expense_estimation::{ExpenseCategory, category_shares, category_totals, lifetime_total};
+use tokyo_lifetime_expense_estimation::export::{write_family_expenses_csv, write_person_expenses_csv};
+use tokyo_lifetime_expense_estimation::rent_vs_buy::RentVsBuyComparison;
+use tokyo_lifetime_expense_estimation::scenario::Scenario;
+
+const USAGE: &str = "\
+usage: tokyo-expense <command> <scenario file (.toml or .json)>
+
+commands:
+    table      yearly expenses by category
+    summary    total expenses by category over the whole span
+    people     yearly expenses by person
+    csv        yearly expenses by category as CSV
+    people-csv yearly expenses by person and category as CSV
+    children   yearly balance for each number of planned children
+    rent-vs-buy cumulative cost of renting and buying and the break-even year";
+
+fn load_scenario(path: &str) -> Result<Scenario, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+    if path.ends_with(".json") {
+        Scenario::from_json_str(&content).map_err(|e| format!("failed to parse {}: {}", path, e))
+    } else {
+        Scenario::from_toml_str(&content).map_err(|e| format!("failed to parse {}: {}", path, e))
+    }
+}
+
+fn category_name(category: ExpenseCategory) -> &'static str {
+    match category {
+        ExpenseCategory::Car => "car",
+        ExpenseCategory::House => "house",
+        ExpenseCategory::Pet => "pet",
+        ExpenseCategory::Birth => "birth",
+        ExpenseCategory::Food => "food",
+        ExpenseCategory::FuelLightWaterGasEtc => "utilities",
+        ExpenseCategory::Furniture => "furniture",
+        ExpenseCategory::Clothing => "clothing",
+        ExpenseCategory::Medical => "medical",
+        ExpenseCategory::Education => "education",
+        ExpenseCategory::ExtraEducation => "extra_edu",
+        ExpenseCategory::ExtracurricularActivities => "activities",
+        ExpenseCategory::Mobile => "mobile",
+        ExpenseCategory::Allowance => "allowance",
+        ExpenseCategory::LivingAlone => "alone",
+        ExpenseCategory::Ceremony => "ceremony",
+        ExpenseCategory::Leisure => "leisure",
+        ExpenseCategory::DriverLincenseAquisition => "license",
+    }
+}
+
+fn print_row(cells: &[String]) {
+    let line: Vec<String> = cells.iter().map(|cell| format!("{:>11}", cell)).collect();
+    println!("{}", line.join(" "));
+}
+
+fn print_table(scenario: &Scenario) {
+    let expenses = scenario.estimate_family_expenses();
+    let mut header = vec!["year".to_string()];
+    header.extend(ExpenseCategory::ALL.into_iter().map(|category| category_name(category).to_string()));
+    header.push("total".to_string());
+    print_row(&header);
+
+    for expense in &expenses {
+        let mut row = vec![expense.year.to_string()];
+        row.extend(expense.amounts().map(|(_, amount)| amount.to_string()));
+        row.push(expense.total().to_string());
+        print_row(&row);
+    }
+}
+
+fn print_summary(scenario: &Scenario) {
+    let expenses = scenario.estimate_family_expenses();
+    let end_year = scenario.start_year + scenario.years as u16;
+    println!("{} - {} ({} years)", scenario.start_year, end_year - 1, scenario.years);
+    for ((category, total), (_, share)) in category_totals(&expenses).into_iter().zip(category_shares(&expenses)) {
+        println!("{:<12} {:>14} {:>6.1}%", category_name(category), total, share * 100.0);
+    }
+    println!("{:<12} {:>14}", "total", lifetime_total(&expenses));
+}
+
+fn print_people(scenario: &Scenario) {
+    let expenses = scenario.estimate_family_expenses();
+    let people = scenario.all_people();
+    let mut header = vec!["year".to_string()];
+    header.extend(people.iter().map(|person| person.name.clone()));
+    print_row(&header);
+
+    for expense in &expenses {
+        let mut row = vec![expense.year.to_string()];
+        row.extend(people.iter().map(|person| {
+            expense.member_expenses.iter()
+                .find(|e| e.name == person.name)
+                .map(|e| e.total().to_string())
+                .unwrap_or_else(|| "-".to_string())
+        }));
+        print_row(&row);
+    }
+}
+
+fn print_children(scenario: &Scenario) {
+    let comparisons = scenario.compare_planned_children();
+    let mut header = vec!["year".to_string()];
+    header.extend(comparisons.iter().map(|comparison| format!("+{} child", comparison.num_planned_children)));
+    print_row(&header);
+
+    for (i, year) in (scenario.start_year..(scenario.start_year + scenario.years as u16)).enumerate() {
+        let mut row = vec![year.to_string()];
+        row.extend(comparisons.iter().map(|comparison| comparison.cash_flows[i].balance.to_string()));
+        print_row(&row);
+    }
+
+    let mut expense_row = vec!["expense".to_string()];
+    expense_row.extend(comparisons.iter().map(|comparison| comparison.lifetime_expense.to_string()));
+    print_row(&expense_row);
+    let mut benefits_row = vec!["benefits".to_string()];
+    benefits_row.extend(comparisons.iter().map(|comparison| comparison.lifetime_benefits.to_string()));
+    print_row(&benefits_row);
+    let mut balance_row = vec!["balance".to_string()];
+    balance_row.extend(comparisons.iter().map(|comparison| comparison.lifetime_balance.to_string()));
+    print_row(&balance_row);
+}
+
+fn print_rent_vs_buy(comparison: &RentVsBuyComparison) {
+    print_row(&[
+        "year", "rent_outlay", "buy_outlay", "rent_net", "buy_net", "loan_balance", "property", "equity", "buy_cost",
+    ].map(String::from));
+    for row in &comparison.yearly {
+        print_row(&[
+            row.year.to_string(),
+            row.rental_outlay.to_string(),
+            row.owned_outlay.to_string(),
+            row.rental_net_cost.to_string(),
+            row.owned_net_cost.to_string(),
+            row.remaining_loan_balance.to_string(),
+            row.property_value.to_string(),
+            row.equity().to_string(),
+            row.owned_economic_cost().to_string(),
+        ]);
+    }
+    match comparison.break_even_year {
+        Some(year) => println!("break-even year: {}", year),
+        None => println!("break-even year: none"),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().collect();
+    let (Some(command), Some(path)) = (args.get(1), args.get(2)) else {
+        eprintln!("{}", USAGE);
+        return ExitCode::FAILURE;
+    };
+    let scenario = match load_scenario(path) {
+        Ok(scenario) => scenario,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        },
+    };
+    match command.as_str() {
+        "table" => print_table(&scenario),
+        "summary" => print_summary(&scenario),
+        "people" => print_people(&scenario),
+        "children" => print_children(&scenario),
+        "rent-vs-buy" => {
+            let Some(rent_vs_buy) = &scenario.rent_vs_buy else {
+                eprintln!("rent_vs_buy is not set in {}", path);
+                return ExitCode::FAILURE;
+            };
+            match rent_vs_buy.compare(&scenario) {
+                Ok(comparison) => print_rent_vs_buy(&comparison),
+                Err(e) => {
+                    eprintln!("failed to compare rent_vs_buy in {}: {}", path, e);
+                    return ExitCode::FAILURE;
+                },
+            }
+        },
+        "csv" | "people-csv" => {
+            let expenses = scenario.estimate_family_expenses();
+            let mut stdout = std::io::stdout().lock();
+            let result = if command == "csv" {
+                write_family_expenses_csv(&mut stdout, &expenses)
+            } else {
+                write_person_expenses_csv(&mut stdout, &expenses)
+            };
+            if let Err(e) = result {
+                eprintln!("failed to write CSV: {}", e);
+                return ExitCode::FAILURE;
+            }
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        },
+    }
+    ExitCode::SUCCESS
+}
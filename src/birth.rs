@@ -0,0 +1,74 @@
+use chrono::{Datelike, Local, TimeZone};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Person;
+use crate::education::EducationPlan;
+
+// 妊婦健診の自己負担（区の受診票で足りない分）
+const PRENATAL_CHECKUP_EXPENSE: u32 = 50000;
+
+// 2022 年度の東京都の正常分娩の平均出産費用と出産育児一時金（直接支払制度で差し引かれる）
+// https://www.mhlw.go.jp/stf/seisakunitsuite/bunya/kenkou_iryou/iryouhoken/shussan/index.html
+const CHILDBIRTH_EXPENSE: u32 = 605261;
+const CHILDBIRTH_LUMP_SUM: u32 = 500000;
+
+// ベビーカー、チャイルドシート、ベビーベッド、肌着などのベビー用品
+const BABY_GOODS_EXPENSE: u32 = 100000;
+
+// 一人の出産にかかる費用
+pub fn estimate_birth_expense() -> u32 {
+    PRENATAL_CHECKUP_EXPENSE + CHILDBIRTH_EXPENSE.saturating_sub(CHILDBIRTH_LUMP_SUM) + BABY_GOODS_EXPENSE
+}
+
+// その年に生まれる子供の出産にかかる費用
+pub fn estimate_family_birth_expense(people: &[Person], year: u16) -> u32 {
+    let num_births = people.iter()
+        .filter(|person| person.is_child && person.birth_date.year() == year as i32)
+        .count() as u32;
+    estimate_birth_expense() * num_births
+}
+
+// これから生まれる予定の子供
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlannedChild {
+    pub name: String,
+
+    // 生まれる予定の年
+    pub birth_year: u16,
+
+    // 範囲で考える場合の最も遅い年（None の場合は birth_year）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub latest_birth_year: Option<u16>,
+
+    // 寿命（None の場合は Assumptions の寿命）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub lifespan_years: Option<u8>,
+
+    // 子供の進路（None の場合は EducationPlan::default）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub education_plan: Option<EducationPlan>,
+}
+
+impl PlannedChild {
+    // 見積もりに使う年（範囲の場合は中間の年）
+    pub fn expected_birth_year(&self) -> u16 {
+        match self.latest_birth_year {
+            Some(latest_birth_year) if self.birth_year < latest_birth_year => (self.birth_year + latest_birth_year) / 2,
+            _ => self.birth_year,
+        }
+    }
+
+    // 学年の始まりの 4 月 2 日に生まれたものとして扱う
+    pub fn to_person(&self) -> Person {
+        Person {
+            name: self.name.clone(),
+            birth_date: Local.with_ymd_and_hms(self.expected_birth_year().into(), 4, 2, 0, 0, 0).unwrap(),
+            is_child: true,
+            income: None,
+            lifespan_years: self.lifespan_years,
+            education_plan: self.education_plan.clone(),
+        }
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::tax::HouseholdResidentTax;
+
+// 保育園
+// 0 〜 2 歳の認可保育園の保育料は世帯の住民税の課税の状況と特別区民税の所得割額で決まる
+// 3 〜 5 歳は幼児教育・保育の無償化で保育料はかからない（副食費のみ）
+// https://www.cfa.go.jp/policies/kokoseido/mushouka/
+// https://www.city.setagaya.lg.jp/mokuji/kodomo/003/001/002/d00005478.html
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum Childcare {
+    Home, // 家庭保育（3 歳から幼稚園）
+    Licensed, // 認可保育園
+    Certified, // 東京都の認証保育所
+}
+
+// 区ごとの保育料の設定
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct DaycareFeeTable {
+    // 認可保育園（0 〜 2 歳、標準時間）の所得割課税世帯の (世帯の所得割額がこの額から, 保育料の月額) を昇順に並べたもの
+    // 住民税非課税世帯は無料
+    pub licensed_monthly_fees: Vec<(u32, u32)>,
+
+    // 認可保育園（0 〜 2 歳、標準時間）の均等割のみ課税世帯の保育料の月額
+    pub licensed_per_capita_only_monthly_fee: u32,
+
+    // 認可保育園の 0 〜 2 歳の保育料が無償になる年（東京都は 2025 年 9 月から第 1 子も無償）
+    pub licensed_free_from_year: Option<u16>,
+
+    // 認証保育所の保育料の月額と区の補助の月額
+    pub certified_monthly_fee: u32,
+    pub certified_monthly_subsidy: u32,
+
+    // 3 〜 5 歳の無償化の上限（認証保育所は認可外の扱い）
+    pub certified_free_limit: u32,
+
+    // 3 〜 5 歳の副食費の月額
+    pub side_dish_monthly_fee: u32,
+}
+
+impl Default for DaycareFeeTable {
+    // 23 区の一般的な値（概算）
+    fn default() -> Self {
+        Self {
+            licensed_monthly_fees: vec![
+                (0, 6000),
+                (48600, 10000),
+                (97000, 18000),
+                (169000, 28000),
+                (235000, 38000),
+                (301000, 48000),
+                (397000, 58000),
+                (500000, 67000),
+            ],
+            licensed_per_capita_only_monthly_fee: 6000,
+            licensed_free_from_year: Some(2026),
+            certified_monthly_fee: 70000,
+            certified_monthly_subsidy: 40000,
+            certified_free_limit: 37000,
+            side_dish_monthly_fee: 4500,
+        }
+    }
+}
+
+impl DaycareFeeTable {
+    // 保育園に通っている場合の年間の費用
+    pub fn estimate_annual_fee(&self, childcare: Childcare, age: u8, year: u16, resident_tax: HouseholdResidentTax) -> u32 {
+        let monthly_fee = match (childcare, age) {
+            (Childcare::Home, _) => 0,
+            (Childcare::Licensed, 0..=2) => {
+                if self.licensed_free_from_year.is_some_and(|free_from_year| free_from_year <= year) {
+                    0
+                } else {
+                    match resident_tax {
+                        HouseholdResidentTax::Exempt => 0,
+                        HouseholdResidentTax::PerCapitaOnly => self.licensed_per_capita_only_monthly_fee,
+                        HouseholdResidentTax::IncomeLevy(municipal_income_levy) => self.licensed_monthly_fees.iter()
+                            .rev()
+                            .find(|(from, _)| *from <= municipal_income_levy)
+                            .map(|(_, fee)| *fee)
+                            .unwrap_or(0),
+                    }
+                }
+            },
+            (Childcare::Certified, 0..=2) => self.certified_monthly_fee.saturating_sub(self.certified_monthly_subsidy),
+            (Childcare::Licensed, _) => self.side_dish_monthly_fee,
+            (Childcare::Certified, _) => {
+                self.certified_monthly_fee.saturating_sub(self.certified_monthly_subsidy + self.certified_free_limit) +
+                    self.side_dish_monthly_fee
+            },
+        };
+        monthly_fee * 12
+    }
+}
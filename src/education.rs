@@ -0,0 +1,237 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::LifeStage;
+use crate::assumptions::Assumptions;
+use crate::daycare::Childcare;
+
+// 公立、私立ごとの学費
+// 公立は文部科学省の子供の学習費調査（学校教育費 + 学校給食費）
+// 私立は LifeStage の東京の私立学校の値
+// Blended は LifeStage の表の値をそのまま使う（進路を指定しない子供の学費）
+// https://www.mext.go.jp/b_menu/toukei/chousa03/gakushuuhi/kekka/k_detail/mext_00001.html
+// https://www.mext.go.jp/a_menu/koutou/shinkou/07021403/1412031_00004.htm
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum SchoolType {
+    Public,
+    Private,
+    Blended, // LifeStage の表の値（受験の塾代は上乗せしない）
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum UniversityType {
+    National, // 国公立
+    PrivateHumanities, // 私立文系
+    PrivateScience, // 私立理系
+    Blended, // LifeStage の表の値（国公立と私立をまとめた平均）
+}
+
+// 高校卒業後の進学先
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum HigherEducation {
+    None, // 就職
+    Vocational {
+        years: u8,
+    }, // 専門学校
+    JuniorCollege, // 短大
+    University,
+}
+
+// 子供ごとの進路
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default = "EducationPlan::base"))]
+pub struct EducationPlan {
+    pub kinder_garden: SchoolType,
+    pub elementary_school: SchoolType,
+    pub middle_school: SchoolType,
+    pub high_school: SchoolType,
+    pub university: UniversityType,
+    pub graduate_school: UniversityType,
+
+    pub gap_years: u8, // 浪人する年数
+    pub higher_education: HigherEducation,
+    pub masters: bool, // 修士
+    pub doctorate: bool, // 博士（修士の後）
+
+    // 小学校に入るまでの保育（保育園の場合は kinder_garden の代わりに 5 歳まで保育園に通う）
+    pub childcare: Childcare,
+    pub childcare_start_age: u8,
+}
+
+impl Default for EducationPlan {
+    // 進路を指定しない子供の進路（LifeStage::new と同じく現役で大学に進んで博士まで、学費は LifeStage の表の値）
+    fn default() -> Self {
+        Self {
+            kinder_garden: SchoolType::Blended,
+            elementary_school: SchoolType::Blended,
+            middle_school: SchoolType::Blended,
+            high_school: SchoolType::Blended,
+            university: UniversityType::Blended,
+            graduate_school: UniversityType::Blended,
+            gap_years: 0,
+            higher_education: HigherEducation::University,
+            masters: true,
+            doctorate: true,
+            childcare: Childcare::Home,
+            childcare_start_age: 1,
+        }
+    }
+}
+
+impl EducationPlan {
+    // 進路を書いた時に省略した項目の値（幼稚園は私立、小学校から高校は公立、国公立大学に現役で進み、大学院には進まない）
+    pub fn base() -> Self {
+        Self {
+            kinder_garden: SchoolType::Private,
+            elementary_school: SchoolType::Public,
+            middle_school: SchoolType::Public,
+            high_school: SchoolType::Public,
+            university: UniversityType::National,
+            graduate_school: UniversityType::National,
+            masters: false,
+            doctorate: false,
+            ..Self::default()
+        }
+    }
+
+    // 高校までは LifeStage::new と同じで、その後は進路による（大学、大学院の年数は assumptions.life_stage_ages から）
+    pub fn get_life_stage(&self, age: u8, assumptions: &Assumptions) -> LifeStage {
+        let ages = &assumptions.life_stage_ages;
+        let high_school_graduation_age = ages.under_graduate;
+        if age < high_school_graduation_age {
+            return LifeStage::new(age, assumptions);
+        }
+
+        // (年数, 状態) を順に並べたもの
+        let mut stages = Vec::new();
+        match self.higher_education {
+            HigherEducation::None => {},
+            HigherEducation::Vocational { years } => {
+                stages.push((self.gap_years, LifeStage::GapYear));
+                stages.push((years, LifeStage::Vocational));
+            },
+            HigherEducation::JuniorCollege => {
+                stages.push((self.gap_years, LifeStage::GapYear));
+                stages.push((2, LifeStage::JuniorCollege));
+            },
+            HigherEducation::University => {
+                stages.push((self.gap_years, LifeStage::GapYear));
+                stages.push((ages.masters.saturating_sub(ages.under_graduate), LifeStage::UnderGraduate));
+                if self.masters {
+                    stages.push((ages.doctorate.saturating_sub(ages.masters), LifeStage::Masters));
+                    if self.doctorate {
+                        stages.push((ages.working.saturating_sub(ages.doctorate), LifeStage::Doctorate));
+                    }
+                }
+            },
+        }
+
+        let mut end_age = high_school_graduation_age;
+        for (years, stage) in stages {
+            end_age = end_age.saturating_add(years);
+            if age < end_age {
+                return stage;
+            }
+        }
+        LifeStage::Working
+    }
+
+    pub fn estimate_annual_tuition(&self, stage: &LifeStage) -> u32 {
+        match stage {
+            LifeStage::KinderGarden => match self.kinder_garden {
+                SchoolType::Public => 61156 + 13415,
+                SchoolType::Private | SchoolType::Blended => stage.estimate_annual_tuition(),
+            },
+            LifeStage::ElementarySchool => match self.elementary_school {
+                SchoolType::Public => 65974 + 39010,
+                SchoolType::Private | SchoolType::Blended => stage.estimate_annual_tuition(),
+            },
+            LifeStage::MiddleSchool => match self.middle_school {
+                SchoolType::Public => 132349 + 37670,
+                SchoolType::Private | SchoolType::Blended => stage.estimate_annual_tuition(),
+            },
+            LifeStage::HighSchool => match self.high_school {
+                SchoolType::Public => 309261,
+                SchoolType::Private | SchoolType::Blended => stage.estimate_annual_tuition(),
+            },
+            LifeStage::UnderGraduate => match self.university {
+                UniversityType::National => 535800,
+                UniversityType::PrivateHumanities => 815069 + 148272,
+                UniversityType::PrivateScience => 1136074 + 179159,
+                UniversityType::Blended => stage.estimate_annual_tuition(),
+            },
+            LifeStage::Masters | LifeStage::Doctorate => match self.graduate_school {
+                UniversityType::National => 535800,
+                UniversityType::PrivateHumanities | UniversityType::PrivateScience | UniversityType::Blended => stage.estimate_annual_tuition(),
+            },
+            _ => stage.estimate_annual_tuition(),
+        }
+    }
+
+    // 保育園に通っている場合はその種類
+    pub fn get_daycare(&self, age: u8) -> Option<Childcare> {
+        if self.childcare != Childcare::Home && self.childcare_start_age <= age && age <= 5 {
+            Some(self.childcare)
+        } else {
+            None
+        }
+    }
+
+    // 入学金、受験料など（大学は併願も含めて 3 校受験する想定）
+    pub fn estimate_initial_school_fees(&self, stage: &LifeStage) -> u32 {
+        match stage {
+            LifeStage::KinderGarden => match self.kinder_garden {
+                SchoolType::Public => 0,
+                SchoolType::Private | SchoolType::Blended => stage.estimate_initial_school_fees(),
+            },
+            LifeStage::ElementarySchool => match self.elementary_school {
+                SchoolType::Public => 0,
+                SchoolType::Private | SchoolType::Blended => stage.estimate_initial_school_fees(),
+            },
+            LifeStage::MiddleSchool => match self.middle_school {
+                SchoolType::Public => 0,
+                SchoolType::Private | SchoolType::Blended => stage.estimate_initial_school_fees(),
+            },
+            LifeStage::HighSchool => match self.high_school {
+                SchoolType::Public => 5650 + 2200 + 23322 * 2, // 都立の入学料、受験料と私立の併願
+                SchoolType::Private | SchoolType::Blended => stage.estimate_initial_school_fees(),
+            },
+            LifeStage::UnderGraduate => match self.university {
+                UniversityType::National => 282000 + 18000 + 17000 + 35000 * 2, // 共通テストと国立 1 校、私立 2 校
+                UniversityType::PrivateHumanities => 225651 + 35000 * 3,
+                UniversityType::PrivateScience => 251029 + 35000 * 3,
+                UniversityType::Blended => stage.estimate_initial_school_fees(),
+            },
+            LifeStage::Masters | LifeStage::Doctorate => match self.graduate_school {
+                UniversityType::National => 282000 + 30000,
+                UniversityType::PrivateHumanities | UniversityType::PrivateScience | UniversityType::Blended => stage.estimate_initial_school_fees(),
+            },
+            _ => stage.estimate_initial_school_fees(),
+        }
+    }
+
+    // 私立小学校、私立中学校の受験のための塾代など（学校外教育費の平均への上乗せ分）
+    pub fn estimate_entrance_exam_prep_expense(&self, age: u8) -> u32 {
+        let mut expense = 0;
+        if self.elementary_school == SchoolType::Private {
+            expense += match age {
+                4 => 400000,
+                5 => 800000,
+                _ => 0,
+            };
+        }
+        if self.middle_school == SchoolType::Private && self.elementary_school == SchoolType::Public {
+            expense += match age {
+                9 => 500000,
+                10 => 700000,
+                11 => 1000000,
+                _ => 0,
+            };
+        }
+        expense
+    }
+}
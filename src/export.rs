@@ -0,0 +1,77 @@
+use std::io::{self, Write};
+
+use crate::{ExpenseCategory, FamilyExpense};
+
+// スプレッドシートに貼り付けるための CSV 出力
+
+// (項目, 列名)
+const PERSON_COLUMNS: [(ExpenseCategory, &str); 14] = [
+    (ExpenseCategory::Clothing, "clothing_expense"),
+    (ExpenseCategory::Food, "food_expense"),
+    (ExpenseCategory::FuelLightWaterGasEtc, "fuel_light_water_gas_etc_expense"),
+    (ExpenseCategory::Furniture, "furniture_expense"),
+    (ExpenseCategory::Medical, "medical_expense"),
+    (ExpenseCategory::Education, "education_expense"),
+    (ExpenseCategory::ExtraEducation, "extra_education_expense"),
+    (ExpenseCategory::ExtracurricularActivities, "extracurricular_activities_expense"),
+    (ExpenseCategory::Mobile, "mobile_expense"),
+    (ExpenseCategory::Allowance, "allowance"),
+    (ExpenseCategory::LivingAlone, "living_alone_expense"),
+    (ExpenseCategory::Ceremony, "ceremony_expense"),
+    (ExpenseCategory::Leisure, "leisure_expense"),
+    (ExpenseCategory::DriverLincenseAquisition, "driver_lincense_aquisition_fees"),
+];
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn write_row<W: Write>(writer: &mut W, fields: &[String]) -> io::Result<()> {
+    let fields: Vec<String> = fields.iter().map(|field| escape(field)).collect();
+    writeln!(writer, "{}", fields.join(","))
+}
+
+// 一年一行
+// 食費、光熱費、家具は世帯のベース分と一人当たりの分を合わせた値
+pub fn write_family_expenses_csv<W: Write>(writer: &mut W, expenses: &[FamilyExpense]) -> io::Result<()> {
+    let mut header = vec!["year".to_string(), "car_expense".to_string(), "house_expense".to_string(), "pet_expense".to_string(), "birth_expense".to_string()];
+    header.extend(PERSON_COLUMNS.iter().map(|(_, column)| column.to_string()));
+    header.push("total".to_string());
+    write_row(writer, &header)?;
+
+    for expense in expenses {
+        let mut row = vec![
+            expense.year.to_string(),
+            expense.car_expense.to_string(),
+            expense.house_expense.to_string(),
+            expense.pet_expense.to_string(),
+            expense.birth_expense.to_string(),
+        ];
+        row.extend(PERSON_COLUMNS.iter().map(|(category, _)| expense.amount(*category).to_string()));
+        row.push(expense.total().to_string());
+        write_row(writer, &row)?;
+    }
+    Ok(())
+}
+
+// 一年 × 一人で一行（世帯のベース分や車、住居、ペット、出産は含まない）
+pub fn write_person_expenses_csv<W: Write>(writer: &mut W, expenses: &[FamilyExpense]) -> io::Result<()> {
+    let mut header = vec!["year".to_string(), "name".to_string()];
+    header.extend(PERSON_COLUMNS.iter().map(|(_, column)| column.to_string()));
+    header.push("total".to_string());
+    write_row(writer, &header)?;
+
+    for expense in expenses {
+        for member_expense in &expense.member_expenses {
+            let mut row = vec![expense.year.to_string(), member_expense.name.clone()];
+            row.extend(PERSON_COLUMNS.iter().map(|(category, _)| member_expense.amount(*category).to_string()));
+            row.push(member_expense.total().to_string());
+            write_row(writer, &row)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,111 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Person;
+use crate::assumptions::Assumptions;
+
+// 公的年金の受給開始年齢
+const PENSION_START_AGE: u8 = 65;
+
+// 昇給カーブ
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum RaiseCurve {
+    // 毎年一定の率で昇給する
+    Constant(f64),
+
+    // (この年齢未満まで, 昇給率) を年齢の昇順に並べたもの
+    // 最後の年齢以降は昇給しない
+    ByAge(Vec<(u8, f64)>),
+}
+
+impl RaiseCurve {
+    pub fn rate(&self, age: u8) -> f64 {
+        match self {
+            Self::Constant(rate) => *rate,
+            Self::ByAge(rates) => rates.iter()
+                .find(|(until_age, _)| age < *until_age)
+                .map(|(_, rate)| *rate)
+                .unwrap_or(0.0),
+        }
+    }
+}
+
+// 一人分の収入の設定
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Income {
+    pub base_year: u16, // monthly_salary がこの年の値
+    pub monthly_salary: u32, // 月給（額面）
+    pub bonus_months: f64, // 賞与が月給の何ヶ月分か
+    pub raise_curve: RaiseCurve,
+    pub retirement_age: u8, // この年齢から給与がなくなる
+    pub annual_pension: u32, // 65 歳から受け取る公的年金の年額
+}
+
+impl Income {
+    // その年の月給（額面）
+    pub fn estimate_monthly_salary(&self, person: &Person, year: u16, assumptions: &Assumptions) -> u32 {
+        let mut salary = self.monthly_salary as f64;
+        for y in self.base_year..year {
+            let Some(age) = person.get_grade_age(y, assumptions) else {
+                continue;
+            };
+            salary *= 1.0 + self.raise_curve.rate(age);
+        }
+        salary as u32
+    }
+
+    pub fn estimate_income(&self, person: &Person, year: u16, assumptions: &Assumptions) -> PersonIncome {
+        let mut income = PersonIncome {
+            name: person.name.clone(),
+            salary: 0,
+            bonus: 0,
+            pension: 0,
+        };
+        let Some(age) = person.get_grade_age(year, assumptions) else {
+            return income;
+        };
+        if age < self.retirement_age {
+            let monthly_salary = self.estimate_monthly_salary(person, year, assumptions);
+            income.salary = monthly_salary * 12;
+            income.bonus = (monthly_salary as f64 * self.bonus_months) as u32;
+        }
+        if PENSION_START_AGE <= age {
+            income.pension = self.annual_pension;
+        }
+        income
+    }
+}
+
+#[derive(Debug)]
+pub struct PersonIncome {
+    pub name: String,
+    pub salary: u32,
+    pub bonus: u32,
+    pub pension: u32,
+}
+
+impl PersonIncome {
+    pub fn total(&self) -> u32 {
+        self.salary + self.bonus + self.pension
+    }
+}
+
+#[derive(Debug)]
+pub struct FamilyIncome {
+    pub member_incomes: Vec<PersonIncome>,
+}
+
+impl FamilyIncome {
+    pub fn total(&self) -> u32 {
+        self.member_incomes.iter().map(|income| income.total()).sum()
+    }
+}
+
+pub fn estimate_family_income(people: &[Person], year: u16, assumptions: &Assumptions) -> FamilyIncome {
+    let member_incomes = people.iter()
+        .filter_map(|person| person.income.as_ref().map(|income| income.estimate_income(person, year, assumptions)))
+        .collect();
+    FamilyIncome { member_incomes }
+}
@@ -0,0 +1,85 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::ExpenseCategory;
+
+// 定数の金額は 2022 〜 2023 年の値なので 2023 年を基準年とする
+pub const BASE_YEAR: u16 = 2023;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum InflationModel {
+    // 全ての項目で同じ年率
+    Flat(f64),
+
+    // 項目ごとの年率（指定のない項目は default_rate）
+    PerCategory {
+        default_rate: f64,
+        rates: Vec<(ExpenseCategory, f64)>,
+    },
+
+    // 基準年を 1.0 とした (年, 物価指数) の表を年の昇順に並べたもの
+    // 表にない年は直前の年の値を使い、表の最初の年より前は 1.0 とする
+    IndexTable(Vec<(u16, f64)>),
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct Inflation {
+    pub base_year: u16,
+    pub model: InflationModel,
+}
+
+impl Default for Inflation {
+    // インフレしない
+    fn default() -> Self {
+        Self {
+            base_year: BASE_YEAR,
+            model: InflationModel::Flat(0.0),
+        }
+    }
+}
+
+impl Inflation {
+    fn compound(&self, rate: f64, year: u16) -> f64 {
+        (1.0 + rate).powi(year as i32 - self.base_year as i32)
+    }
+
+    // 基準年を 1.0 とした項目ごとの物価指数
+    pub fn index(&self, category: ExpenseCategory, year: u16) -> f64 {
+        match &self.model {
+            InflationModel::Flat(rate) => self.compound(*rate, year),
+            InflationModel::PerCategory { default_rate, rates } => {
+                let rate = rates.iter()
+                    .find(|(c, _)| *c == category)
+                    .map(|(_, rate)| *rate)
+                    .unwrap_or(*default_rate);
+                self.compound(rate, year)
+            },
+            InflationModel::IndexTable(_) => self.general_index(year),
+        }
+    }
+
+    // 実質値への換算に使う全体の物価指数
+    pub fn general_index(&self, year: u16) -> f64 {
+        match &self.model {
+            InflationModel::Flat(rate) => self.compound(*rate, year),
+            InflationModel::PerCategory { default_rate, .. } => self.compound(*default_rate, year),
+            InflationModel::IndexTable(table) => table.iter()
+                .rev()
+                .find(|(y, _)| *y <= year)
+                .map(|(_, index)| *index)
+                .unwrap_or(1.0),
+        }
+    }
+
+    // 基準年の金額を名目の金額にする
+    pub fn apply(&self, category: ExpenseCategory, year: u16, amount: u32) -> u32 {
+        (amount as f64 * self.index(category, year)) as u32
+    }
+
+    // 名目の金額を基準年の金額（実質値）にする
+    pub fn to_real(&self, year: u16, amount: u32) -> u32 {
+        (amount as f64 / self.general_index(year)) as u32
+    }
+}
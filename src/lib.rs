@@ -1,4 +1,35 @@
+use std::borrow::Cow;
+
 use chrono::{DateTime, Local, TimeZone};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+pub mod asset;
+pub mod assumptions;
+pub mod benefit;
+pub mod birth;
+pub mod daycare;
+pub mod education;
+pub mod export;
+pub mod income;
+pub mod inflation;
+pub mod monte_carlo;
+pub mod property;
+pub mod rent_vs_buy;
+pub mod scenario;
+pub mod tax;
+pub mod transport;
+pub mod vehicle;
+
+use assumptions::{Assumptions, lookup_age_table};
+use benefit::FamilyBenefits;
+use education::EducationPlan;
+use income::{FamilyIncome, Income};
+use inflation::Inflation;
+use property::{EnergyClass, OwnedProperty};
+use scenario::Scenario;
+use tax::{HouseholdResidentTax, PersonTax};
+use vehicle::VehicleSpec;
 
 // 一人暮らしの契約、引越し
 const INITIAL_LIVING_ALONE_EXPENSE: u32 = 480000;
@@ -57,7 +88,198 @@ const DRIVER_LICENCE_AQUISITION_EXPENSE: u32 = 302489;
 // 携帯の月額料金と買い替え料金を均した額の予測値
 const MOBILE_EXPENSE: u32 = 3000 * 12 + 10000;
 
-#[derive(Debug)]
+// 携帯電話を持つ年齢
+const MOBILE_START_AGE: u8 = 10;
+
+// 衣類の年齢ごとの比率 (この年齢から, 比率)
+// 以下のスプレッドシートの計算により、年齢ごとの比率を決めた
+// https://docs.google.com/spreadsheets/d/1O-reA7is_DVPTW-k1EU4e9Hc5f5Q6bPBGPWfEgM3Z_I/edit?usp=sharing
+const CLOTHING_RATES: [(u8, f64); 25] = [
+    (0, 0.904479703),
+    (1, 0.7528228185),
+    (2, 0.7174627804),
+    (3, 0.6809886838),
+    (4, 0.7081517868),
+    (5, 0.61088451),
+    (6, 0.6791319195),
+    (7, 0.6869846738),
+    (8, 0.7422166185),
+    (9, 0.7433940788),
+    (10, 0.8035622993),
+    (11, 0.8303631068),
+    (12, 0.9398669132),
+    (13, 0.8913555495),
+    (14, 0.7917605243),
+    (15, 1.108464734),
+    (18, 1.425168944),
+    (22, 1.741873153),
+    (24, 1.900225258),
+    (26, 2.058577363),
+    (30, 1.583521049),
+    (35, 1.266816839),
+    (40, 0.9501126292),
+    (50, 0.7917605243),
+    (60, 0.6334084194),
+];
+
+// 食費の年齢ごとの比率 (この年齢から, 比率)
+// 以下のスプレッドシートの計算により、年齢ごとの比率を決めた
+// https://docs.google.com/spreadsheets/d/1O-reA7is_DVPTW-k1EU4e9Hc5f5Q6bPBGPWfEgM3Z_I/edit?usp=sharing
+const FOOD_RATES: [(u8, f64); 21] = [
+    (0, 0.3461706859),
+    (1, 0.4840146905),
+    (2, 0.6132080103),
+    (3, 0.6628411973),
+    (4, 0.7058329511),
+    (5, 0.7106333491),
+    (6, 0.7893866661),
+    (7, 0.821466159),
+    (8, 0.8453341988),
+    (9, 0.8866998607),
+    (10, 0.9566342765),
+    (11, 0.9319469605),
+    (12, 1.050059803),
+    (13, 1.133703856),
+    (14, 1.151011456),
+    (15, 1.174031685),
+    (18, 1.070440654),
+    (30, 1.093460883),
+    (50, 1.047420425),
+    (65, 0.9783597377),
+    (75, 0.8632585921),
+];
+
+// 医療費の (この年齢から, 年額)
+// https://docs.google.com/spreadsheets/d/1O-reA7is_DVPTW-k1EU4e9Hc5f5Q6bPBGPWfEgM3Z_I/edit#gid=227018819
+const MEDICAL_EXPENSES: [(u8, u32); 28] = [
+    (0, 15027),
+    (1, 16168),
+    (2, 12232),
+    (3, 13030),
+    (4, 14814),
+    (5, 14209),
+    (6, 20840),
+    (7, 22906),
+    (8, 26489),
+    (9, 27330),
+    (10, 23284),
+    (11, 23256),
+    (12, 23608),
+    (13, 29707),
+    (14, 21903),
+    (15, 19878),
+    (20, 19923),
+    (25, 24676),
+    (30, 28861),
+    (35, 32068),
+    (40, 36435),
+    (45, 44213),
+    (50, 56040),
+    (55, 88814),
+    (60, 88268),
+    (65, 110511),
+    (70, 93249),
+    (75, 57867),
+];
+
+// 学校外教育費（塾、予備校）の (この年齢から, 年額)
+const EXTRA_EDUCATION_EXPENSES: [(u8, u32); 19] = [
+    (0, 26809),
+    (1, 34193),
+    (2, 35877),
+    (3, 44351),
+    (4, 55861),
+    (5, 55048),
+    (6, 87941),
+    (7, 91968),
+    (8, 109036),
+    (9, 129767),
+    (10, 182600),
+    (11, 224482),
+    (12, 202826),
+    (13, 251784),
+    (14, 387870),
+    (15, 400000),
+    (16, 500000),
+    (17, 1000000),
+    (18, 0),
+];
+
+// 習い事の (この年齢から, 年額)
+const EXTRACURRICULAR_ACTIVITIES_EXPENSES: [(u8, u32); 19] = [
+    (0, 28090),
+    (1, 38108),
+    (2, 45284),
+    (3, 49827),
+    (4, 77783),
+    (5, 90609),
+    (6, 100251),
+    (7, 112791),
+    (8, 117021),
+    (9, 120734),
+    (10, 113773),
+    (11, 102553),
+    (12, 88245),
+    (13, 74513),
+    (14, 72897),
+    (15, 60000),
+    (16, 50000),
+    (17, 40000),
+    (18, 0),
+];
+
+// お小遣い、プレゼントの (この年齢から, 年額)
+const ALLOWANCES: [(u8, u32); 19] = [
+    (0, 51284),
+    (1, 33325),
+    (2, 29098),
+    (3, 33985),
+    (4, 30977),
+    (5, 31913),
+    (6, 43203),
+    (7, 35816),
+    (8, 39770),
+    (9, 41291),
+    (10, 42502),
+    (11, 51609),
+    (12, 61493),
+    (13, 76845),
+    (14, 80005),
+    (15, 85000),
+    (16, 90000),
+    (17, 80000),
+    (18, 20000),
+];
+
+// 一人暮らしの子供と働いている子供へのお小遣い、プレゼント
+const LIVING_ALONE_CHILD_ALLOWANCE: u32 = 30000;
+const WORKING_CHILD_ALLOWANCE: u32 = 20000;
+
+// レジャー、旅行の (この年齢から, 年額)
+const LEISURE_EXPENSES: [(u8, u32); 19] = [
+    (0, 79163),
+    (1, 108488),
+    (2, 125141),
+    (3, 125299),
+    (4, 141137),
+    (5, 146071),
+    (6, 152708),
+    (7, 168690),
+    (8, 182467),
+    (9, 171124),
+    (10, 177438),
+    (11, 177201),
+    (12, 173200),
+    (13, 174405),
+    (14, 123861),
+    (15, 175000),
+    (16, 175000),
+    (17, 120000),
+    (18, 175000),
+];
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Person {
     pub name: String,
 
@@ -65,18 +287,40 @@ pub struct Person {
 
     // 子供の場合 true, 配偶者や自分は false
     pub is_child: bool,
+
+    // 給与や年金などの収入（収入がない場合は None）
+    pub income: Option<Income>,
+
+    // 寿命（None の場合は Assumptions の寿命）
+    pub lifespan_years: Option<u8>,
+
+    // 子供の進路（None の場合は EducationPlan::default）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub education_plan: Option<EducationPlan>,
 }
 
 impl Person {
+    // 子供の進路
+    pub fn get_education_plan(&self) -> Cow<'_, EducationPlan> {
+        match &self.education_plan {
+            Some(plan) => Cow::Borrowed(plan),
+            None => Cow::Owned(EducationPlan::default()),
+        }
+    }
+
+    // 学校などの状態
+    pub fn get_life_stage(&self, age: u8, assumptions: &Assumptions) -> LifeStage {
+        self.get_education_plan().get_life_stage(age, assumptions)
+    }
 
     // 学年を決める4月2日時点での年齢
     // 生まれてない or 寿命を過ぎてたら None
-    pub fn get_grade_age(&self, year: u16) -> Option<u8> {
+    pub fn get_grade_age(&self, year: u16, assumptions: &Assumptions) -> Option<u8> {
         let tz = self.birth_date.timezone();
         let base_date = tz.with_ymd_and_hms(year.into(), 4, 2, 0, 0, 0).unwrap();
         match base_date.years_since(self.birth_date) {
             None => None,
-            Some(years) => if years > LIFESPAN_YEARS.into() {
+            Some(years) => if years > self.lifespan_years.unwrap_or(assumptions.lifespan_years).into() {
                 None
             } else {
                 Some(years as u8)
@@ -85,47 +329,152 @@ impl Person {
     }
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Car {
     pub start_year: u16, // include
     pub end_year: u16, // not include
+    // spec がある場合は使わない（spec から求める）
+    #[cfg_attr(feature = "serde", serde(default))]
     pub annual_car_type_tax: u32, // 自動車（種別割）税の年割
+    #[cfg_attr(feature = "serde", serde(default))]
     pub annual_weight_tax: u32, // 自動車重量税
+    #[cfg_attr(feature = "serde", serde(default))]
     pub annual_liability_insurance_fee: u32, // 自賠責の年割
     pub annual_optional_insurance_fee: u32, // 任意保険の年割
+    #[cfg_attr(feature = "serde", serde(default))]
     pub annual_inspection_fee: u32, // 車検代の年割
     pub annual_gas_expense: u32, // 年間のガソリン代
     pub annual_consumables_expense: u32, // 年間の消耗品
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub monthly_parking_fee: u32, // 月極駐車場（自宅に駐車場がある場合は 0）
     pub down_payment: u32,
     pub loan: Option<YearlyLoan>,
+
+    // 車両価格（None の場合は頭金とローンの借入額の合計）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub price: Option<u32>,
+
+    // end_year に売る、下取りに出す時の価値の下がり方
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub depreciation: Depreciation,
+
+    // 諸元（ある場合は自動車税、重量税、自賠責、車検の費用を実際に払う年に計上する）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub spec: Option<VehicleSpec>,
+}
+
+// 車の価値の下がり方
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum Depreciation {
+    #[default]
+    None, // 売却、下取りしない
+    DecliningBalance { annual_rate: f64 }, // 毎年一定の割合で下がる
+    Table(Vec<(u16, f64)>), // 車両価格に対する (この年数から, 残価率) を昇順に並べたもの
+}
+
+impl Depreciation {
+    // 車両価格に対する残価率
+    pub fn residual_rate(&self, years: u16) -> f64 {
+        match self {
+            Depreciation::None => 0.0,
+            Depreciation::DecliningBalance { annual_rate } => (1.0 - annual_rate).max(0.0).powi(years as i32),
+            Depreciation::Table(rates) => rates.iter()
+                .take_while(|(from_years, _)| *from_years <= years)
+                .last()
+                .map_or(1.0, |(_, rate)| *rate),
+        }
+    }
 }
 
 impl Car {
     pub fn estimate_expense(&self, year: u16) -> u32 {
+        self.estimate_running_expense(year) + self.estimate_loan_payment(year, self.loan_schedule().as_ref())
+    }
+
+    // ローンの支払い以外
+    pub fn estimate_running_expense(&self, year: u16) -> u32 {
         let mut expense = 0;
         if self.start_year <= year && year < self.end_year {
             // いわゆる維持費
-            expense += self.annual_car_type_tax + 
-                self.annual_weight_tax + 
-                self.annual_liability_insurance_fee + 
-                self.annual_optional_insurance_fee + 
-                self.annual_inspection_fee + 
+            expense += self.annual_optional_insurance_fee + 
                 self.annual_gas_expense + 
-                self.annual_consumables_expense;
+                self.annual_consumables_expense +
+                self.monthly_parking_fee * 12;
+            match &self.spec {
+                Some(spec) => {
+                    expense += spec.estimate_car_type_tax(year) + spec.estimate_inspection_expense(year);
+                    if spec.registration_year == year {
+                        // 新車登録
+                        expense += spec.estimate_registration_expense();
+                    }
+                },
+                None => {
+                    expense += self.annual_car_type_tax + 
+                        self.annual_weight_tax + 
+                        self.annual_liability_insurance_fee + 
+                        self.annual_inspection_fee;
+                },
+            }
         };
         if self.start_year == year {
             // 初期費
             expense += self.down_payment;
         };
-        if let Some(loan) = &self.loan {
-            // ローンの支払い
-            if self.start_year <= year && year < (self.start_year + loan.payment_years) {
-                expense += loan.calcurate_yearly_payment();
-            }
-        };
         expense
     }
+
+    // end_year に売却、下取りした時の金額
+    pub fn estimate_resale_value(&self, year: u16) -> u32 {
+        if year != self.end_year {
+            return 0;
+        }
+        let price = self.price.unwrap_or_else(|| self.down_payment + self.loan.as_ref().map_or(0, |loan| loan.amount));
+        (price as f64 * self.depreciation.residual_rate(self.end_year - self.start_year)) as u32
+    }
+
+    pub fn loan_schedule(&self) -> Option<LoanSchedule> {
+        self.loan.as_ref().map(|loan| loan.schedule())
+    }
+
+    // schedule は loan_schedule（何年分も見積もる時は一度だけ作って使い回す）
+    pub fn estimate_loan_payment(&self, year: u16, schedule: Option<&LoanSchedule>) -> u32 {
+        match schedule {
+            Some(schedule) if self.start_year <= year => schedule.payment_in_year(year - self.start_year),
+            _ => 0,
+        }
+    }
+}
+
+// 同じ車を一定の間隔で買い替え続ける
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CarReplacement {
+    pub template: Car, // 最初の車（start_year から end_year までの年数ごとに買い替える）
+    pub last_year: u16, // 車を手放す年（この年で最後の車を売る）
+}
+
+impl CarReplacement {
+    pub fn cars(&self) -> Vec<Car> {
+        let interval_years = self.template.end_year.saturating_sub(self.template.start_year).max(1);
+        (self.template.start_year..self.last_year).step_by(interval_years as usize).map(|start_year| {
+            let mut car = Car {
+                start_year,
+                end_year: (start_year + interval_years).min(self.last_year),
+                ..self.template.clone()
+            };
+            if let Some(spec) = car.spec.as_mut() {
+                // 買い替えた車の新車登録の年
+                spec.registration_year += start_year - self.template.start_year;
+            }
+            car
+        }).collect()
+    }
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct House {
     pub start_year: u16, // include
     pub end_year: u16, // not include
@@ -133,73 +482,586 @@ pub struct House {
     pub kind: HouseKind,
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type", rename_all = "snake_case"))]
 pub enum HouseKind {
     Rental {
         rent: u32,
+        #[cfg_attr(feature = "serde", serde(default))]
+        contract: RentalContract,
     },
     Own {
         down_payment: u32,
         loan: Option<YearlyLoan>,
+        // 固定資産税、管理費、修繕費、保険、購入時の諸費用（None の場合は考えない）
+        #[cfg_attr(feature = "serde", serde(default))]
+        property: Option<OwnedProperty>,
     },
 }
 
+// 賃貸の契約の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum LeaseType {
+    #[default]
+    Ordinary, // 普通借家（更新できる）
+    FixedTerm {
+        renewable: bool,
+    }, // 定期借家（再契約できない場合は end_year より前でも最初の契約期間の終わりに退去する）
+}
+
+// 賃貸の契約条件（月数は家賃の何か月分か）
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct RentalContract {
+    pub lease_type: LeaseType,
+    pub deposit_months: f64, // 敷金
+    pub key_money_months: f64, // 礼金
+    pub brokerage_fee_months: f64, // 仲介手数料（税込）
+    pub guarantor_initial_fee_months: f64, // 保証会社の初回保証料
+    pub guarantor_annual_fee: u32, // 保証会社の年間保証料（2 年目から）
+    pub key_exchange_fee: u32, // 鍵交換
+    pub fire_insurance_fee: u32, // 火災保険（入居時と更新ごと）
+    pub renewal_interval_years: u16, // 契約期間（普通借家は更新、定期借家は再契約）
+    pub renewal_fee_months: f64, // 更新料（定期借家の場合は再契約料）
+    pub move_out_cleaning_fee: u32, // 退去時の原状回復、クリーニング（敷金から差し引く）
+    pub rent_increases: Vec<(u16, u32)>, // 家賃の (入居からこの年数から, 家賃)（順番は問わない）
+}
+
+impl Default for RentalContract {
+    // 敷金 1 か月、礼金 1 か月、2 年ごとに更新料 1 か月
+    fn default() -> Self {
+        Self {
+            lease_type: LeaseType::Ordinary,
+            deposit_months: 1.0,
+            key_money_months: 1.0,
+            brokerage_fee_months: 0.0,
+            guarantor_initial_fee_months: 0.0,
+            guarantor_annual_fee: 0,
+            key_exchange_fee: 0,
+            fire_insurance_fee: 0,
+            renewal_interval_years: 2,
+            renewal_fee_months: 1.0,
+            move_out_cleaning_fee: 0,
+            rent_increases: Vec::new(),
+        }
+    }
+}
+
+impl RentalContract {
+    // 入居から years 年目の家賃
+    pub fn rent_in_year(&self, rent: u32, years: u16) -> u32 {
+        self.rent_increases.iter()
+            .filter(|(from_years, _)| *from_years <= years)
+            .max_by_key(|(from_years, _)| *from_years)
+            .map_or(rent, |(_, rent)| *rent)
+    }
+
+    fn months_of(rent: u32, months: f64) -> u32 {
+        (rent as f64 * months) as u32
+    }
+
+    // 入居時の敷金、礼金、仲介手数料、保証料、鍵交換、火災保険
+    pub fn estimate_initial_expense(&self, rent: u32) -> u32 {
+        Self::months_of(rent, self.deposit_months + self.key_money_months + self.brokerage_fee_months + self.guarantor_initial_fee_months) +
+            self.key_exchange_fee +
+            self.fire_insurance_fee
+    }
+
+    // 入居から years 年目の更新料（再契約料）、保証料、火災保険
+    pub fn estimate_renewal_expense(&self, rent: u32, years: u16) -> u32 {
+        let mut expense = 0;
+        if 0 < years {
+            expense += self.guarantor_annual_fee;
+        }
+        if 0 < years && 0 < self.renewal_interval_years && years.is_multiple_of(self.renewal_interval_years) {
+            expense += Self::months_of(rent, self.renewal_fee_months) + self.fire_insurance_fee;
+        }
+        expense
+    }
+}
+
 impl House {
     pub fn estimate_expense(&self, year: u16) -> u32 {
+        self.estimate_running_expense(year) + self.estimate_loan_payment(year, self.loan_schedule().as_ref())
+    }
+
+    // 住み終わる年（not include、再契約できない定期借家は最初の契約期間の終わり）
+    pub fn move_out_year(&self) -> u16 {
+        match &self.kind {
+            HouseKind::Rental { contract, .. } if contract.lease_type == (LeaseType::FixedTerm { renewable: false }) && 0 < contract.renewal_interval_years => {
+                self.end_year.min(self.start_year + contract.renewal_interval_years)
+            },
+            _ => self.end_year,
+        }
+    }
+
+    // ローンの支払い以外
+    pub fn estimate_running_expense(&self, year: u16) -> u32 {
         let mut expense = 0;
         if self.start_year == year {
             expense += self.moving_expense;
         }
         match &self.kind {
-            HouseKind::Rental { rent } => {
-                if self.start_year <= year && year < self.end_year {
+            HouseKind::Rental { rent, contract } => {
+                if self.start_year <= year && year < self.move_out_year() {
+                    let residence_years = year - self.start_year;
                     if self.start_year == year {
-                        expense += rent * 2; // 敷金礼金
+                        expense += contract.estimate_initial_expense(*rent); // 敷金礼金など
                     } else {
-                        let residence_years = year - self.start_year;
-                        if residence_years % 2 == 0 {
-                            expense += rent; // 契約更新料
-                        };
+                        // 契約更新料
+                        expense += contract.estimate_renewal_expense(contract.rent_in_year(*rent, residence_years), residence_years);
                     };
-                    expense += rent * 12; // 家賃
+                    expense += contract.rent_in_year(*rent, residence_years) * 12; // 家賃
                 };
             },
-            HouseKind::Own { down_payment, loan } => {
+            HouseKind::Own { down_payment, loan, property } => {
                 if self.start_year == year {
                     // 初期費
                     expense += down_payment;
                 };
-                if let Some(loan) = loan {
-                    if self.start_year <= year && year < (self.start_year + loan.payment_years) {
-                        expense += loan.calcurate_yearly_payment();
+                if let Some(property) = property {
+                    if self.start_year == year {
+                        // 仲介手数料、登記費用、不動産取得税
+                        expense += property.estimate_acquisition_expense(self.start_year, loan.as_ref());
                     }
-                };
+                    if self.start_year <= year && year < self.end_year {
+                        // 固定資産税、管理費、修繕費、保険
+                        expense += property.estimate_annual_expense(self.start_year, year);
+                    }
+                }
             },
         }
         expense
     }
+
+    pub fn loan_schedule(&self) -> Option<LoanSchedule> {
+        match &self.kind {
+            HouseKind::Own { loan: Some(loan), .. } => Some(loan.schedule()),
+            _ => None,
+        }
+    }
+
+    // schedule は loan_schedule（何年分も見積もる時は一度だけ作って使い回す）
+    pub fn estimate_loan_payment(&self, year: u16, schedule: Option<&LoanSchedule>) -> u32 {
+        match schedule {
+            Some(schedule) if self.start_year <= year => schedule.payment_in_year(year - self.start_year),
+            _ => 0,
+        }
+    }
+
+    // 退去する年（move_out_year）に戻る敷金（原状回復の費用を引いた額）
+    pub fn estimate_deposit_refund(&self, year: u16, inflation: &Inflation) -> u32 {
+        match &self.kind {
+            HouseKind::Rental { rent, contract } if year == self.move_out_year() => {
+                let deposit = inflation.apply(ExpenseCategory::House, self.start_year, RentalContract::months_of(*rent, contract.deposit_months));
+                let cleaning_fee = inflation.apply(ExpenseCategory::House, year, contract.move_out_cleaning_fee);
+                deposit.saturating_sub(cleaning_fee)
+            },
+            _ => 0,
+        }
+    }
+
+    // 住宅ローン控除の控除額（税額による上限の前、schedule は loan_schedule）
+    pub fn estimate_mortgage_deduction(&self, year: u16, schedule: Option<&LoanSchedule>) -> u32 {
+        match (&self.kind, schedule) {
+            (HouseKind::Own { property, .. }, Some(schedule)) if self.start_year <= year && year < self.end_year => {
+                let year_end_balance = schedule.remaining_balance(year - self.start_year + 1);
+                match property {
+                    Some(property) => property.estimate_mortgage_deduction(self.start_year, year, year_end_balance),
+                    // 物件の情報がない場合は省エネ性能を既定（その他の住宅）にして、その他の新築は対象外なので中古として扱う
+                    None => property::estimate_mortgage_deduction(EnergyClass::default(), false, self.start_year, year, year_end_balance),
+                }
+            },
+            _ => 0,
+        }
+    }
+}
+
+// ペットの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum PetSpecies {
+    SmallDog,
+    MediumDog,
+    LargeDog,
+    Cat,
+}
+
+// 医療費と保険料のペットの年齢ごとの比率 (この年齢から, 比率)
+// アニコムの家庭どうぶつ白書の年齢別の診療費を参考にした
+const PET_MEDICAL_RATES: [(u8, f64); 4] = [
+    (0, 1.0),
+    (7, 1.5),
+    (10, 2.0),
+    (13, 2.5),
+];
+
+// ケージ、トイレ、首輪などの飼い始めの用品と初回のワクチン、避妊・去勢手術
+const PET_INITIAL_EXPENSE: u32 = 80000;
+
+impl PetSpecies {
+    // 年間のフード、おやつ代
+    // https://petfood.or.jp/data/
+    pub fn annual_food_expense(&self) -> u32 {
+        match self {
+            PetSpecies::SmallDog => 60000,
+            PetSpecies::MediumDog => 80000,
+            PetSpecies::LargeDog => 120000,
+            PetSpecies::Cat => 50000,
+        }
+    }
+
+    // 若い時の年間の医療費（ワクチン、狂犬病予防注射、フィラリア予防などを含む）
+    pub fn annual_medical_expense(&self) -> u32 {
+        match self {
+            PetSpecies::SmallDog => 70000,
+            PetSpecies::MediumDog => 80000,
+            PetSpecies::LargeDog => 100000,
+            PetSpecies::Cat => 40000,
+        }
+    }
+
+    // 若い時の年間のペット保険料（70% 補償）
+    pub fn annual_insurance_fee(&self) -> u32 {
+        match self {
+            PetSpecies::SmallDog => 40000,
+            PetSpecies::MediumDog => 50000,
+            PetSpecies::LargeDog => 70000,
+            PetSpecies::Cat => 30000,
+        }
+    }
+
+    // 年間のトリミング代
+    pub fn annual_trimming_expense(&self) -> u32 {
+        match self {
+            PetSpecies::SmallDog => 72000,
+            PetSpecies::MediumDog => 60000,
+            PetSpecies::LargeDog => 48000,
+            PetSpecies::Cat => 0,
+        }
+    }
+
+    // 火葬、供養
+    pub fn end_of_life_expense(&self) -> u32 {
+        match self {
+            PetSpecies::SmallDog => 40000,
+            PetSpecies::MediumDog => 50000,
+            PetSpecies::LargeDog => 70000,
+            PetSpecies::Cat => 35000,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Pet {
+    pub start_year: u16, // include
+    pub end_year: u16, // not include、最後の年に亡くなる想定
+    pub birth_year: Option<u16>, // None の場合は start_year に生まれた子を迎える
+    pub species: PetSpecies,
+    pub acquisition_expense: u32, // 生体の購入費、譲渡費用
+}
+
+impl Pet {
+    pub fn estimate_expense(&self, year: u16) -> u32 {
+        if year < self.start_year || self.end_year <= year {
+            return 0;
+        }
+        let age = year.saturating_sub(self.birth_year.unwrap_or(self.start_year));
+        let medical_rate = lookup_age_table(&PET_MEDICAL_RATES, age.min(u8::MAX as u16) as u8);
+
+        // 年間の費用
+        let mut expense = self.species.annual_food_expense() +
+            (self.species.annual_medical_expense() as f64 * medical_rate) as u32 +
+            (self.species.annual_insurance_fee() as f64 * medical_rate) as u32 +
+            self.species.annual_trimming_expense();
+        if self.start_year == year {
+            // 初期費
+            expense += self.acquisition_expense + PET_INITIAL_EXPENSE;
+        }
+        if self.end_year == year + 1 {
+            // 看取り
+            expense += self.species.end_of_life_expense();
+        }
+        expense
+    }
+}
+
+// 返済方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum RepaymentMethod {
+    #[default]
+    EqualPayment, // 元利均等（毎月の返済額が一定）
+    EqualPrincipal, // 元金均等（毎月の元金が一定）
+}
+
+// 繰上げ返済の方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum PrepaymentKind {
+    ShortenTerm, // 期間短縮型（毎月の返済額はそのまま）
+    ReducePayment, // 返済額軽減型（完済の時期はそのまま）
 }
 
+// 繰上げ返済
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Prepayment {
+    pub year_index: u16, // 返済開始から何年目（0 から）の年末に返済するか
+    pub amount: Option<u32>, // None の場合は全額
+    pub kind: PrepaymentKind,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct YearlyLoan {
     pub interest_rate: f64,
     pub payment_years: u16,
     pub amount: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub repayment_method: RepaymentMethod,
+
+    // 金利の (返済開始からこの年数から, 金利) を昇順に並べたもの（固定期間の後の変動金利や金利の予想）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rate_changes: Vec<(u16, f64)>,
+
+    // 変動金利の 5 年ルールと 125% ルール（元利均等のみ）
+    // 返済額は 5 年ごとに見直して前の 1.25 倍まで、払いきれない利息は未払利息として最後に精算する
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub applies_payment_change_rules: bool,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub prepayments: Vec<Prepayment>,
+}
+
+// 返済予定表の一行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoanPayment {
+    pub index: u16, // 返済開始からの月数または年数（0 から）
+    pub principal: u32, // 元金
+    pub interest: u32, // 利息
+    pub prepayment: u32, // 繰上げ返済
+    pub balance: u32, // 返済後の残高
+}
+
+impl LoanPayment {
+    pub fn total(&self) -> u32 {
+        self.principal + self.interest + self.prepayment
+    }
+}
+
+// 年ごとの返済予定表（一度作って年ごとの返済額と残高を引く）
+#[derive(Debug, Clone)]
+pub struct LoanSchedule {
+    pub amount: u32,
+    pub yearly_payments: Vec<LoanPayment>,
+}
+
+impl LoanSchedule {
+    // 返済開始から year_index 年目（0 から）の返済額（繰上げ返済を含む）
+    pub fn payment_in_year(&self, year_index: u16) -> u32 {
+        self.yearly_payments.get(year_index as usize).map_or(0, |payment| payment.total())
+    }
+
+    // paid_years 年分を返済した後の残高
+    pub fn remaining_balance(&self, paid_years: u16) -> u32 {
+        match paid_years.checked_sub(1) {
+            None => self.amount,
+            Some(year_index) => self.yearly_payments.get(year_index as usize).map_or(0, |payment| payment.balance),
+        }
+    }
+}
+
+// 5 年ルールで返済額を見直す間隔と 125% ルールの上限
+const PAYMENT_CHANGE_INTERVAL_YEARS: u16 = 5;
+const PAYMENT_CHANGE_LIMIT_RATIO: f64 = 1.25;
+
+// 元利均等の毎月の返済額
+fn calcurate_equal_payment(balance: u32, monthly_interest_rate: f64, months: u16) -> u32 {
+    if months == 0 {
+        return balance;
+    }
+    if monthly_interest_rate == 0.0 {
+        return balance / months as u32;
+    }
+    let pvif = (monthly_interest_rate + 1.0).powf(months as f64);
+    let payment = monthly_interest_rate / (pvif - 1.0) * -(balance as f64 * pvif);
+    (-payment) as u32
 }
 
 impl YearlyLoan {
-    fn calcurate_monthly_payment(&self) -> u32 {
-        let interest_rate = self.interest_rate / 12.0;
-        let months = self.payment_years * 12;
-        let pvif = (interest_rate + 1.0).powf(months as f64);
-        let payment = interest_rate / (pvif - 1.0) * -(self.amount as f64 * pvif);
-        (-payment) as u32
+    // 返済開始から year_index 年目（0 から）の金利
+    pub fn interest_rate_in_year(&self, year_index: u16) -> f64 {
+        self.rate_changes.iter()
+            .take_while(|(from_year_index, _)| *from_year_index <= year_index)
+            .last()
+            .map_or(self.interest_rate, |(_, rate)| *rate)
     }
 
+    // 最初の年の返済額（元利均等で金利が変わらなければ毎年同じ）
     pub fn calcurate_yearly_payment(&self) -> u32 {
-        self.calcurate_monthly_payment() * 12
+        self.calcurate_payment_in_year(0)
+    }
+
+    // 返済開始から year_index 年目（0 から）の返済額（繰上げ返済を含む）
+    // 毎回返済予定表を作るので、何年分も引く時は schedule を使う
+    pub fn calcurate_payment_in_year(&self, year_index: u16) -> u32 {
+        self.schedule().payment_in_year(year_index)
+    }
+
+    // paid_years 年分を返済した後の残高（calcurate_payment_in_year と同じく毎回返済予定表を作る）
+    pub fn calcurate_remaining_balance(&self, paid_years: u16) -> u32 {
+        self.schedule().remaining_balance(paid_years)
+    }
+
+    pub fn schedule(&self) -> LoanSchedule {
+        LoanSchedule {
+            amount: self.amount,
+            yearly_payments: self.yearly_schedule(),
+        }
+    }
+
+    // 期間短縮型の繰上げ返済の後、同じ返済額で完済するまでの月数
+    fn calcurate_shortened_months(&self, balance: u32, monthly_interest_rate: f64, monthly_payment: u32, monthly_principal: u32, remaining_months: u16) -> u16 {
+        let months = match self.repayment_method {
+            RepaymentMethod::EqualPayment if monthly_interest_rate == 0.0 => (balance as f64 / monthly_payment as f64).ceil(),
+            RepaymentMethod::EqualPayment => {
+                let ratio = 1.0 - balance as f64 * monthly_interest_rate / monthly_payment as f64;
+                if ratio <= 0.0 {
+                    return remaining_months;
+                }
+                (-ratio.ln() / (monthly_interest_rate + 1.0).ln()).ceil()
+            },
+            RepaymentMethod::EqualPrincipal => (balance as f64 / monthly_principal as f64).ceil(),
+        };
+        (months as u16).min(remaining_months)
+    }
+
+    // 月ごとの返済予定表（利息は円未満切り捨て、最後の月で残高と未払利息を精算）
+    pub fn monthly_schedule(&self) -> Vec<LoanPayment> {
+        let mut months = self.payment_years * 12;
+        let mut balance = self.amount;
+        let mut unpaid_interest = 0;
+        let mut interest_rate = self.interest_rate_in_year(0) / 12.0;
+        let mut monthly_payment = calcurate_equal_payment(balance, interest_rate, months);
+        let mut monthly_principal = balance / months.max(1) as u32;
+        let uses_payment_change_rules = self.applies_payment_change_rules && self.repayment_method == RepaymentMethod::EqualPayment;
+
+        let mut schedule = Vec::new();
+        let mut month = 0;
+        while month < months && 0 < balance + unpaid_interest {
+            let year_index = month / 12;
+            if 0 < year_index && month % 12 == 0 {
+                // 金利の見直し
+                let remaining_months = months - month;
+                let new_interest_rate = self.interest_rate_in_year(year_index) / 12.0;
+                if uses_payment_change_rules {
+                    interest_rate = new_interest_rate;
+                    if year_index.is_multiple_of(PAYMENT_CHANGE_INTERVAL_YEARS) {
+                        let payment = calcurate_equal_payment(balance + unpaid_interest, interest_rate, remaining_months);
+                        monthly_payment = payment.min((monthly_payment as f64 * PAYMENT_CHANGE_LIMIT_RATIO) as u32);
+                    }
+                } else if new_interest_rate != interest_rate {
+                    interest_rate = new_interest_rate;
+                    monthly_payment = calcurate_equal_payment(balance, interest_rate, remaining_months);
+                }
+            }
+
+            let interest = (balance as f64 * interest_rate) as u32 + unpaid_interest;
+            let (principal, paid_interest) = if month + 1 == months {
+                (balance, interest)
+            } else {
+                match self.repayment_method {
+                    RepaymentMethod::EqualPayment => {
+                        let paid_interest = interest.min(monthly_payment);
+                        ((monthly_payment - paid_interest).min(balance), paid_interest)
+                    },
+                    RepaymentMethod::EqualPrincipal => (monthly_principal.min(balance), interest),
+                }
+            };
+            unpaid_interest = interest - paid_interest;
+            balance -= principal;
+            let mut payment = LoanPayment { index: month, principal, interest: paid_interest, prepayment: 0, balance };
+
+            // 年末の繰上げ返済
+            if month % 12 == 11 {
+                for prepayment in self.prepayments.iter().filter(|prepayment| prepayment.year_index == year_index) {
+                    let amount = prepayment.amount.unwrap_or(balance).min(balance);
+                    balance -= amount;
+                    payment.prepayment += amount;
+                    if balance == 0 {
+                        payment.prepayment += unpaid_interest;
+                        unpaid_interest = 0;
+                        continue;
+                    }
+                    let remaining_months = months - month - 1;
+                    match prepayment.kind {
+                        PrepaymentKind::ShortenTerm => {
+                            months = month + 1 + self.calcurate_shortened_months(balance, interest_rate, monthly_payment, monthly_principal, remaining_months);
+                        },
+                        PrepaymentKind::ReducePayment => {
+                            monthly_payment = calcurate_equal_payment(balance + unpaid_interest, interest_rate, remaining_months);
+                            monthly_principal = balance / remaining_months.max(1) as u32;
+                        },
+                    }
+                }
+                payment.balance = balance;
+            }
+
+            schedule.push(payment);
+            month += 1;
+        }
+        schedule
+    }
+
+    // 年ごとの返済予定表
+    pub fn yearly_schedule(&self) -> Vec<LoanPayment> {
+        self.monthly_schedule().chunks(12).enumerate().map(|(year_index, payments)| {
+            LoanPayment {
+                index: year_index as u16,
+                principal: payments.iter().map(|payment| payment.principal).sum(),
+                interest: payments.iter().map(|payment| payment.interest).sum(),
+                prepayment: payments.iter().map(|payment| payment.prepayment).sum(),
+                balance: payments.last().map_or(self.amount, |payment| payment.balance),
+            }
+        }).collect()
+    }
+}
+
+// LifeStage::new の状態が始まる年齢（学年を決める 4 月 2 日時点）
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default))]
+pub struct LifeStageAges {
+    pub kinder_garden: u8,
+    pub elementary_school: u8,
+    pub middle_school: u8,
+    pub high_school: u8,
+    pub under_graduate: u8,
+    pub masters: u8,
+    pub doctorate: u8,
+    pub working: u8,
+}
+
+impl Default for LifeStageAges {
+    // 大学 4 年、修士 2 年、博士 2 年
+    fn default() -> Self {
+        Self {
+            kinder_garden: 3,
+            elementary_school: 6,
+            middle_school: 12,
+            high_school: 15,
+            under_graduate: 18,
+            masters: 22,
+            doctorate: 24,
+            working: 26,
+        }
     }
 }
 
 #[derive(PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
 pub enum LifeStage {
     PreSchool,
     KinderGarden,
@@ -209,6 +1071,9 @@ pub enum LifeStage {
     UnderGraduate,
     Masters,
     Doctorate,
+    GapYear, // 浪人
+    Vocational, // 専門学校
+    JuniorCollege, // 短大
     Working,
 }
 
@@ -221,17 +1086,26 @@ impl LifeStage {
     // https://eic.obunsha.co.jp/pdf/educational_info/2022/0822_1.pdf
     // https://www.mext.go.jp/a_menu/koutou/shinkou/07021403/1412031_00004.htm
 
-    pub fn new(age: u8)  -> LifeStage {
-        match age {
-            0..=2 => Self::PreSchool,
-            3..=5 => Self::KinderGarden,
-            6..=11 => Self::ElementarySchool,
-            12..=14 => Self::MiddleSchool,
-            15..=17 => Self::HighSchool,
-            18..=21 => Self::UnderGraduate,
-            22..=23 => Self::Masters,
-            24..=25 => Self::Doctorate,
-            _ => Self::Working,
+    pub fn new(age: u8, assumptions: &Assumptions) -> LifeStage {
+        let ages = &assumptions.life_stage_ages;
+        if age < ages.kinder_garden {
+            Self::PreSchool
+        } else if age < ages.elementary_school {
+            Self::KinderGarden
+        } else if age < ages.middle_school {
+            Self::ElementarySchool
+        } else if age < ages.high_school {
+            Self::MiddleSchool
+        } else if age < ages.under_graduate {
+            Self::HighSchool
+        } else if age < ages.masters {
+            Self::UnderGraduate
+        } else if age < ages.doctorate {
+            Self::Masters
+        } else if age < ages.working {
+            Self::Doctorate
+        } else {
+            Self::Working
         }
     }
 
@@ -245,6 +1119,9 @@ impl LifeStage {
             Self::UnderGraduate => 967288,
             Self::Masters => 776040,
             Self::Doctorate => 628729,
+            Self::GapYear => 800000, // 予備校
+            Self::Vocational => 1000000,
+            Self::JuniorCollege => 700000 + 170000,
             Self::Working => 0,
         }
     }
@@ -259,6 +1136,9 @@ impl LifeStage {
             Self::UnderGraduate => 1643466 - 967288 + 261004 * 3,
             Self::Masters => 76206 + 202598 * 3,
             Self::Doctorate => 51842 + 189623 * 3,
+            Self::GapYear => 100000,
+            Self::Vocational => 160000 + 20000,
+            Self::JuniorCollege => 240000 + 35000 * 2,
             Self::Working => 0,
         }
     }
@@ -273,272 +1153,161 @@ impl LifeStage {
             Self::UnderGraduate => true,
             Self::Masters => true,
             Self::Doctorate => true,
+            Self::GapYear => false,
+            Self::Vocational => true,
+            Self::JuniorCollege => true,
             Self::Working => false,
         }
     }
 }
 
 // 衣類
-// 以下のスプレッドシートの計算により、年齢ごとの比率を決めた
-// https://docs.google.com/spreadsheets/d/1O-reA7is_DVPTW-k1EU4e9Hc5f5Q6bPBGPWfEgM3Z_I/edit?usp=sharing
-pub fn estimate_clothing_expense(age: u8, is_child: bool) -> u32 {
+pub fn estimate_clothing_expense(age: u8, is_child: bool, stage: &LifeStage, assumptions: &Assumptions) -> u32 {
     if is_child {
-        let stage = LifeStage::new(age);
         // 仕送りを想定している場合はそちらに含まれるので 0 を返す
-        if stage.might_need_support_living_alone() || stage == LifeStage::Working {
+        if stage.might_need_support_living_alone() || *stage == LifeStage::Working {
             return 0;
         }
     }
-    let rate = match age {
-        0 => 0.904479703,
-        1 => 0.7528228185,
-        2 => 0.7174627804,
-        3 => 0.6809886838,
-        4 => 0.7081517868,
-        5 => 0.61088451,
-        6 => 0.6791319195,
-        7 => 0.6869846738,
-        8 => 0.7422166185,
-        9 => 0.7433940788,
-        10 => 0.8035622993,
-        11 => 0.8303631068,
-        12 => 0.9398669132,
-        13 => 0.8913555495,
-        14 => 0.7917605243,
-        15..=17 => 1.108464734,
-        18..=21 => 1.425168944,
-        22..=23 => 1.741873153,
-        24..=25 => 1.900225258,
-        26..=29 => 2.058577363,
-        30..=34 => 1.583521049,
-        35..=39 => 1.266816839,
-        40..=49 => 0.9501126292,
-        50..=59 => 0.7917605243,
-        _ => 0.6334084194,
-    };
-    (CLOTHING_EXPENSE as f64 * rate) as u32
+    let rate = lookup_age_table(&assumptions.clothing_rates, age);
+    (assumptions.clothing_expense as f64 * rate) as u32
 }
 
 // 食費
-// 以下のスプレッドシートの計算により、年齢ごとの比率を決めた
-// https://docs.google.com/spreadsheets/d/1O-reA7is_DVPTW-k1EU4e9Hc5f5Q6bPBGPWfEgM3Z_I/edit?usp=sharing
-pub fn estimate_person_food_expense(age: u8, is_child: bool) -> u32 {
+pub fn estimate_person_food_expense(age: u8, is_child: bool, stage: &LifeStage, assumptions: &Assumptions) -> u32 {
     if is_child {
-        let stage = LifeStage::new(age);
         // 仕送りを想定している場合はそちらに含まれるので 0 を返す
-        if stage.might_need_support_living_alone() || stage == LifeStage::Working {
+        if stage.might_need_support_living_alone() || *stage == LifeStage::Working {
             return 0;
         }
     }
-    let rate = match age {
-        0 => 0.3461706859,
-        1 => 0.4840146905,
-        2 => 0.6132080103,
-        3 => 0.6628411973,
-        4 => 0.7058329511,
-        5 => 0.7106333491,
-        6 => 0.7893866661,
-        7 => 0.821466159,
-        8 => 0.8453341988,
-        9 => 0.8866998607,
-        10 => 0.9566342765,
-        11 => 0.9319469605,
-        12 => 1.050059803,
-        13 => 1.133703856,
-        14 => 1.151011456,
-        15..=17 => 1.174031685,
-        18..=29 => 1.070440654,
-        30..=49 => 1.093460883,
-        50..=64 => 1.047420425,
-        65..=74 => 0.9783597377,
-        _ => 0.8632585921,
-    };
-    (PERSON_FOOD_EXPENSE as f64 * rate) as u32
+    let rate = lookup_age_table(&assumptions.food_rates, age);
+    (assumptions.person_food_expense as f64 * rate) as u32
 }
 
 // 医療費
-// https://docs.google.com/spreadsheets/d/1O-reA7is_DVPTW-k1EU4e9Hc5f5Q6bPBGPWfEgM3Z_I/edit#gid=227018819
-pub fn estimate_medical_expense(age: u8, is_child: bool) -> u32 {
+pub fn estimate_medical_expense(age: u8, is_child: bool, stage: &LifeStage, assumptions: &Assumptions) -> u32 {
     if is_child {
-        let stage = LifeStage::new(age);
-
         // 仕送りを想定している場合はそちらに含む
-        if stage.might_need_support_living_alone() || stage == LifeStage::Working {
+        if stage.might_need_support_living_alone() || *stage == LifeStage::Working {
             return 0;
         }
     }
-    match age {
-        0 => 15027,
-        1 => 16168,
-        2 => 12232,
-        3 => 13030,
-        4 => 14814,
-        5 => 14209,
-        6 => 20840,
-        7 => 22906,
-        8 => 26489,
-        9 => 27330,
-        10 => 23284,
-        11 => 23256,
-        12 => 23608,
-        13 => 29707,
-        14 => 21903,
-        15..=19 => 19878,
-        20..=24 => 19923,
-        25..=29 => 24676,
-        30..=34 => 28861,
-        35..=39 => 32068,
-        40..=44 => 36435,
-        45..=49 => 44213,
-        50..=54 => 56040,
-        55..=59 => 88814,
-        60..=64 => 88268,
-        65..=69 => 110511,
-        70..=74 => 93249,
-        _ => 57867,
-    }
+    lookup_age_table(&assumptions.medical_expenses, age)
 }
 
 // 学校外教育費（塾、予備校）
-pub fn estimate_extra_education_expense(age: u8) -> u32 {
-    match age {
-        0 => 26809,
-        1 => 34193,
-        2 => 35877,
-        3 => 44351,
-        4 => 55861,
-        5 => 55048,
-        6 => 87941,
-        7 => 91968,
-        8 => 109036,
-        9 => 129767,
-        10 => 182600,
-        11 => 224482,
-        12 => 202826,
-        13 => 251784,
-        14 => 387870,
-        15 => 400000,
-        16 => 500000,
-        17 => 1000000,
-        _ => 0,
-    }
+pub fn estimate_extra_education_expense(age: u8, assumptions: &Assumptions) -> u32 {
+    lookup_age_table(&assumptions.extra_education_expenses, age)
 }
 
 // 習い事
-pub fn estimate_extracurricular_activities_expense(age: u8) -> u32 {
-    match age {
-        0 => 28090,
-        1 => 38108,
-        2 => 45284,
-        3 => 49827,
-        4 => 77783,
-        5 => 90609,
-        6 => 100251,
-        7 => 112791,
-        8 => 117021,
-        9 => 120734,
-        10 => 113773,
-        11 => 102553,
-        12 => 88245,
-        13 => 74513,
-        14 => 72897,
-        15 => 60000,
-        16 => 50000,
-        17 => 40000,
-        _ => 0,
-    }
+pub fn estimate_extracurricular_activities_expense(age: u8, assumptions: &Assumptions) -> u32 {
+    lookup_age_table(&assumptions.extracurricular_activities_expenses, age)
 }
 
 // お小遣い、プレゼント（クリスマス、誕生日、ご褒美等）
-pub fn estimate_allowance(age: u8, is_child: bool) -> u32 {
+pub fn estimate_allowance(age: u8, is_child: bool, stage: &LifeStage, assumptions: &Assumptions) -> u32 {
     if is_child {
-        let stage = LifeStage::new(age);
-
         if stage.might_need_support_living_alone() {
-            return 30000;
+            return assumptions.living_alone_child_allowance;
         }
-        if stage == LifeStage::Working {
-            return 20000;
+        if *stage == LifeStage::Working {
+            return assumptions.working_child_allowance;
         }
     };
-    match age {
-        0 => 51284,
-        1 => 33325,
-        2 => 29098,
-        3 => 33985,
-        4 => 30977,
-        5 => 31913,
-        6 => 43203,
-        7 => 35816,
-        8 => 39770,
-        9 => 41291,
-        10 => 42502,
-        11 => 51609,
-        12 => 61493,
-        13 => 76845,
-        14 => 80005,
-        15 => 85000,
-        16 => 90000,
-        17 => 80000,
-        _ => 20000,
-    }
+    lookup_age_table(&assumptions.allowances, age)
 }
 
 // 冠婚葬祭
-pub fn estimate_ceremony_expense(age: u8, is_child: bool) -> u32 {
+pub fn estimate_ceremony_expense(age: u8, is_child: bool, assumptions: &Assumptions) -> u32 {
     let mut expense = 0;
 
     // 子供の結婚
-    if is_child && age == MARRIAGE_AGE {
-        expense += CHILD_MARRIAGE_SUPPORT_EXPENSE;
+    if is_child && age == assumptions.marriage_age {
+        expense += assumptions.child_marriage_support_expense;
     }
 
     // 両親の葬式
-    if !is_child && age == (LIFESPAN_YEARS - DIFF_FROM_PARENT_AGE) {
-        expense += PARENT_FUNERAL_EXPENSE * 2;
+    if !is_child && age == assumptions.parent_funeral_age() {
+        expense += assumptions.parent_funeral_expense * 2;
     }
 
     expense
 }
 
 // レジャー、旅行
-pub fn estimate_leisure_expense(age: u8) -> u32 {
-    match age {
-        0 => 79163,
-        1 => 108488,
-        2 => 125141,
-        3 => 125299,
-        4 => 141137,
-        5 => 146071,
-        6 => 152708,
-        7 => 168690,
-        8 => 182467,
-        9 => 171124,
-        10 => 177438,
-        11 => 177201,
-        12 => 173200,
-        13 => 174405,
-        14 => 123861,
-        15 => 175000,
-        16 => 175000,
-        17 => 120000,
-        _ => 175000,
-    }
+pub fn estimate_leisure_expense(age: u8, assumptions: &Assumptions) -> u32 {
+    lookup_age_table(&assumptions.leisure_expenses, age)
 }
 
 // 車の免許取得
-pub fn estimate_driver_lincense_aquisition_fees(age: u8) -> u32 {
-    if age == DRIVER_LICENCE_AQUISITION_AGE {
-        DRIVER_LICENCE_AQUISITION_EXPENSE
+pub fn estimate_driver_lincense_aquisition_fees(age: u8, assumptions: &Assumptions) -> u32 {
+    if age == assumptions.driver_licence_aquisition_age {
+        assumptions.driver_licence_aquisition_expense
     } else {
         0
     }
 }
 
+// 支出の項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum ExpenseCategory {
+    Car,
+    House,
+    Pet,
+    Birth,
+    Food,
+    FuelLightWaterGasEtc,
+    Furniture,
+    Clothing,
+    Medical,
+    Education,
+    ExtraEducation,
+    ExtracurricularActivities,
+    Mobile,
+    Allowance,
+    LivingAlone,
+    Ceremony,
+    Leisure,
+    DriverLincenseAquisition,
+}
+
+impl ExpenseCategory {
+    pub const ALL: [ExpenseCategory; 18] = [
+        Self::Car,
+        Self::House,
+        Self::Pet,
+        Self::Birth,
+        Self::Food,
+        Self::FuelLightWaterGasEtc,
+        Self::Furniture,
+        Self::Clothing,
+        Self::Medical,
+        Self::Education,
+        Self::ExtraEducation,
+        Self::ExtracurricularActivities,
+        Self::Mobile,
+        Self::Allowance,
+        Self::LivingAlone,
+        Self::Ceremony,
+        Self::Leisure,
+        Self::DriverLincenseAquisition,
+    ];
+}
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FamilyExpense {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub year: u16,
     pub car_expense: u32,
     pub house_expense: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pet_expense: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub birth_expense: u32, // 妊婦健診、出産費用（出産育児一時金を除く）、ベビー用品
     pub food_expense: u32,
     pub fuel_light_water_gas_etc_expense: u32,
     pub furniture_expense: u32,
@@ -546,6 +1315,7 @@ pub struct FamilyExpense {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct PersonExpense {
     pub name: String,
     pub clothing_expense: u32,
@@ -564,31 +1334,154 @@ pub struct PersonExpense {
     pub driver_lincense_aquisition_fees: u32,
 }
 
-pub fn estimate_family_expenses(people: Vec<Person>, cars: Vec<Car>, houses: Vec<House>, start_year: u16, years: u8) -> Vec<FamilyExpense> {
+impl FamilyExpense {
+    // 全ての金額を項目ごとに変換したもの
+    fn map_amounts(&self, f: impl Fn(ExpenseCategory, u32) -> u32) -> FamilyExpense {
+        FamilyExpense {
+            year: self.year,
+            car_expense: f(ExpenseCategory::Car, self.car_expense),
+            house_expense: f(ExpenseCategory::House, self.house_expense),
+            pet_expense: f(ExpenseCategory::Pet, self.pet_expense),
+            birth_expense: f(ExpenseCategory::Birth, self.birth_expense),
+            food_expense: f(ExpenseCategory::Food, self.food_expense),
+            fuel_light_water_gas_etc_expense: f(ExpenseCategory::FuelLightWaterGasEtc, self.fuel_light_water_gas_etc_expense),
+            furniture_expense: f(ExpenseCategory::Furniture, self.furniture_expense),
+            member_expenses: self.member_expenses.iter().map(|e| e.map_amounts(&f)).collect(),
+        }
+    }
+
+    // 名目の金額を基準年の金額（実質値）にしたもの
+    pub fn to_real(&self, inflation: &Inflation) -> FamilyExpense {
+        self.map_amounts(|_, amount| inflation.to_real(self.year, amount))
+    }
+
+    // 世帯のベース分と一人ずつの分を合わせた項目ごとの金額
+    pub fn amount(&self, category: ExpenseCategory) -> u32 {
+        let family_amount = match category {
+            ExpenseCategory::Car => self.car_expense,
+            ExpenseCategory::House => self.house_expense,
+            ExpenseCategory::Pet => self.pet_expense,
+            ExpenseCategory::Birth => self.birth_expense,
+            ExpenseCategory::Food => self.food_expense,
+            ExpenseCategory::FuelLightWaterGasEtc => self.fuel_light_water_gas_etc_expense,
+            ExpenseCategory::Furniture => self.furniture_expense,
+            _ => 0,
+        };
+        family_amount + self.member_expenses.iter().map(|e| e.amount(category)).sum::<u32>()
+    }
+
+    pub fn amounts(&self) -> impl Iterator<Item = (ExpenseCategory, u32)> + '_ {
+        ExpenseCategory::ALL.into_iter().map(|category| (category, self.amount(category)))
+    }
+
+    pub fn total(&self) -> u32 {
+        self.amounts().map(|(_, amount)| amount).sum()
+    }
+}
+
+impl PersonExpense {
+    pub fn amount(&self, category: ExpenseCategory) -> u32 {
+        match category {
+            ExpenseCategory::Car => 0,
+            ExpenseCategory::House => 0,
+            ExpenseCategory::Pet => 0,
+            ExpenseCategory::Birth => 0,
+            ExpenseCategory::Food => self.food_expense,
+            ExpenseCategory::FuelLightWaterGasEtc => self.fuel_light_water_gas_etc_expense,
+            ExpenseCategory::Furniture => self.furniture_expense,
+            ExpenseCategory::Clothing => self.clothing_expense,
+            ExpenseCategory::Medical => self.medical_expense,
+            ExpenseCategory::Education => self.education_expense,
+            ExpenseCategory::ExtraEducation => self.extra_education_expense,
+            ExpenseCategory::ExtracurricularActivities => self.extracurricular_activities_expense,
+            ExpenseCategory::Mobile => self.mobile_expense,
+            ExpenseCategory::Allowance => self.allowance,
+            ExpenseCategory::LivingAlone => self.living_alone_expense,
+            ExpenseCategory::Ceremony => self.ceremony_expense,
+            ExpenseCategory::Leisure => self.leisure_expense,
+            ExpenseCategory::DriverLincenseAquisition => self.driver_lincense_aquisition_fees,
+        }
+    }
+
+    pub fn amounts(&self) -> impl Iterator<Item = (ExpenseCategory, u32)> + '_ {
+        ExpenseCategory::ALL.into_iter().map(|category| (category, self.amount(category)))
+    }
+
+    pub fn total(&self) -> u32 {
+        self.amounts().map(|(_, amount)| amount).sum()
+    }
+
+    fn map_amounts(&self, f: impl Fn(ExpenseCategory, u32) -> u32) -> PersonExpense {
+        PersonExpense {
+            name: self.name.clone(),
+            clothing_expense: f(ExpenseCategory::Clothing, self.clothing_expense),
+            food_expense: f(ExpenseCategory::Food, self.food_expense),
+            fuel_light_water_gas_etc_expense: f(ExpenseCategory::FuelLightWaterGasEtc, self.fuel_light_water_gas_etc_expense),
+            furniture_expense: f(ExpenseCategory::Furniture, self.furniture_expense),
+            medical_expense: f(ExpenseCategory::Medical, self.medical_expense),
+            education_expense: f(ExpenseCategory::Education, self.education_expense),
+            extra_education_expense: f(ExpenseCategory::ExtraEducation, self.extra_education_expense),
+            extracurricular_activities_expense: f(ExpenseCategory::ExtracurricularActivities, self.extracurricular_activities_expense),
+            mobile_expense: f(ExpenseCategory::Mobile, self.mobile_expense),
+            allowance: f(ExpenseCategory::Allowance, self.allowance),
+            living_alone_expense: f(ExpenseCategory::LivingAlone, self.living_alone_expense),
+            ceremony_expense: f(ExpenseCategory::Ceremony, self.ceremony_expense),
+            leisure_expense: f(ExpenseCategory::Leisure, self.leisure_expense),
+            driver_lincense_aquisition_fees: f(ExpenseCategory::DriverLincenseAquisition, self.driver_lincense_aquisition_fees),
+        }
+    }
+}
+
+// 年ごとの税金（保育料、給付の算定に使う）
+fn estimate_yearly_taxes(scenario: &Scenario, people: &[Person]) -> Vec<Vec<PersonTax>> {
+    (scenario.start_year..(scenario.start_year + scenario.years as u16))
+        .map(|year| tax::estimate_family_taxes(people, year, &scenario.assumptions))
+        .collect()
+}
+
+// 金額は scenario.assumptions.inflation による名目値（ローンの返済額は契約時の金額のまま）
+pub fn estimate_family_expenses(scenario: &Scenario) -> Vec<FamilyExpense> {
+    let people = scenario.all_people();
+    let yearly_taxes = estimate_yearly_taxes(scenario, &people);
+    let house_loan_schedules: Vec<Option<LoanSchedule>> = scenario.houses.iter().map(|house| house.loan_schedule()).collect();
+    estimate_family_expenses_with_taxes(scenario, &people, &scenario.all_cars(), &house_loan_schedules, &yearly_taxes)
+}
+
+// people, cars は予定の子供、買い替えの車を含めたもの、house_loan_schedules は scenario.houses の順の返済予定表
+// yearly_taxes は start_year からの年ごとの税金
+fn estimate_family_expenses_with_taxes(scenario: &Scenario, people: &[Person], cars: &[Car], house_loan_schedules: &[Option<LoanSchedule>], yearly_taxes: &[Vec<PersonTax>]) -> Vec<FamilyExpense> {
+    let Scenario { start_year, transports, houses, pets, assumptions, .. } = scenario;
+    let car_loan_schedules: Vec<Option<LoanSchedule>> = cars.iter().map(|car| car.loan_schedule()).collect();
     let mut expenses = Vec::new();
 
-    for year in start_year..(start_year + years as u16) {
-        let car_expense = cars.iter().map(|car| car.estimate_expense(year)).fold(0, |sum, e| sum + e);
-        let house_expense = houses.iter().map(|house| house.estimate_expense(year)).fold(0, |sum, e| sum + e);
+    for (year, taxes) in (*start_year..).zip(yearly_taxes) {
+        let car_expense: u32 = cars.iter().map(|car| car.estimate_running_expense(year)).sum::<u32>() +
+            transports.iter().map(|transport| transport.estimate_expense(year)).sum::<u32>();
+        let house_expense: u32 = houses.iter().map(|house| house.estimate_running_expense(year)).sum();
+        let pet_expense: u32 = pets.iter().map(|pet| pet.estimate_expense(year)).sum();
+        let birth_expense = birth::estimate_family_birth_expense(people, year);
 
         // per family expense
-        let base_food_expense = BASE_FOOD_EXPENSE;
-        let base_fuel_light_water_gas_etc_expense = BASE_FUEL_LIGHT_WATER_GAS_ETC_EXPENSE;
-        let base_furniture_expense = BASE_FURNITURE_EXPENSE;
+        let base_food_expense = assumptions.base_food_expense;
+        let base_fuel_light_water_gas_etc_expense = assumptions.base_fuel_light_water_gas_etc_expense;
+        let base_furniture_expense = assumptions.base_furniture_expense;
+
+        // 保育料の算定に使う世帯の住民税の課税の状況
+        let resident_tax = HouseholdResidentTax::new(taxes);
 
         let mut member_expenses = Vec::new();
 
-        for person in &people {
-            let Some(age) = person.get_grade_age(year) else {
+        for person in people {
+            let Some(age) = person.get_grade_age(year, assumptions) else {
                 continue;
             };
 
             // 学校など現在の状態
             // 一人暮らしの状態
-            let stage = LifeStage::new(age);
+            let stage = person.get_life_stage(age, assumptions);
             let needs_living_alone_expense = stage.might_need_support_living_alone();
             let (needs_school_initial_fees, needs_initial_living_alone_expense) = if let Some(prev_age) = age.checked_sub(1) {
-                let prev_stage = LifeStage::new(prev_age);
+                let prev_stage = person.get_life_stage(prev_age, assumptions);
                 (
                     person.is_child && stage != prev_stage,
                     person.is_child && !prev_stage.might_need_support_living_alone() && needs_living_alone_expense,
@@ -598,72 +1491,78 @@ pub fn estimate_family_expenses(people: Vec<Person>, cars: Vec<Car>, houses: Vec
             };
 
             // 衣類
-            let clothing_expense = estimate_clothing_expense(age, person.is_child);
+            let clothing_expense = estimate_clothing_expense(age, person.is_child, &stage, assumptions);
 
             // 食費
-            let food_expense = estimate_person_food_expense(age, person.is_child);
+            let food_expense = estimate_person_food_expense(age, person.is_child, &stage, assumptions);
 
             // 一人当たり光熱、ガス、水道、電気など
             let fuel_light_water_gas_etc_expense = if !needs_living_alone_expense && stage != LifeStage::Working {
-                PERSON_FUEL_LIGHT_WATER_GAS_ETC_EXPENSE
+                assumptions.person_fuel_light_water_gas_etc_expense
             } else {
                 0
             };
 
             // 一人当たり家具
             let furniture_expense = if !needs_living_alone_expense && stage != LifeStage::Working {
-                PERSON_FURNITURE_EXPENSE
+                assumptions.person_furniture_expense
             } else {
                 0
             };
 
             // 医療費
-            let medical_expense = estimate_medical_expense(age, person.is_child);
+            let medical_expense = estimate_medical_expense(age, person.is_child, &stage, assumptions);
 
             // 保育費、学費
-            let mut education_expense = if needs_school_initial_fees {
-                stage.estimate_initial_school_fees()
+            let plan = person.get_education_plan();
+            let education_expense = if let Some(childcare) = plan.get_daycare(age) {
+                assumptions.daycare.estimate_annual_fee(childcare, age, year, resident_tax)
             } else {
-                0
+                let mut education_expense = if needs_school_initial_fees {
+                    plan.estimate_initial_school_fees(&stage)
+                } else {
+                    0
+                };
+                education_expense += plan.estimate_annual_tuition(&stage);
+                education_expense
             };
-            education_expense += stage.estimate_annual_tuition();
 
             // 学校外教育費（塾、予備校）
-            let extra_education_expense = estimate_extra_education_expense(age);
+            let extra_education_expense = estimate_extra_education_expense(age, assumptions) + plan.estimate_entrance_exam_prep_expense(age);
 
             // 習い事
-            let extracurricular_activities_expense = estimate_extracurricular_activities_expense(age);
+            let extracurricular_activities_expense = estimate_extracurricular_activities_expense(age, assumptions);
 
-            // 携帯電話（10歳から持つものとする）
-            let mobile_expense = if 10 <= age {
-                MOBILE_EXPENSE
+            // 携帯電話
+            let mobile_expense = if assumptions.mobile_start_age <= age {
+                assumptions.mobile_expense
             } else {
                 0
             };
 
             // お小遣い、プレゼント（クリスマス、誕生日、ご褒美等）
-            let allowance = estimate_allowance(age, person.is_child);
+            let allowance = estimate_allowance(age, person.is_child, &stage, assumptions);
 
             // 一人暮らし開始
             let mut living_alone_expense = if needs_initial_living_alone_expense {
-                INITIAL_LIVING_ALONE_EXPENSE
+                assumptions.initial_living_alone_expense
             } else {
                 0
             };
 
             // 仕送り
             if needs_living_alone_expense {
-                living_alone_expense += ANNUAL_LIVING_ALONE_EXPENSE;
+                living_alone_expense += assumptions.annual_living_alone_expense;
             }
 
             // 冠婚葬祭
-            let ceremony_expense = estimate_ceremony_expense(age, person.is_child);
+            let ceremony_expense = estimate_ceremony_expense(age, person.is_child, assumptions);
 
             // レジャー、旅行
-            let leisure_expense = estimate_leisure_expense(age);
+            let leisure_expense = estimate_leisure_expense(age, assumptions);
 
             // 車の免許取得
-            let driver_lincense_aquisition_fees = estimate_driver_lincense_aquisition_fees(age);
+            let driver_lincense_aquisition_fees = estimate_driver_lincense_aquisition_fees(age, assumptions);
 
             member_expenses.push(PersonExpense {
                 name: person.name.clone(),
@@ -683,16 +1582,120 @@ pub fn estimate_family_expenses(people: Vec<Person>, cars: Vec<Car>, houses: Vec
                 driver_lincense_aquisition_fees,
             });
         }
-        expenses.push(FamilyExpense {
+        let mut expense = FamilyExpense {
+            year,
             car_expense,
             house_expense,
+            pet_expense,
+            birth_expense,
             food_expense: base_food_expense,
             fuel_light_water_gas_etc_expense: base_fuel_light_water_gas_etc_expense,
             furniture_expense: base_furniture_expense,
             member_expenses,
-        });
+        }.map_amounts(|category, amount| assumptions.inflation.apply(category, year, amount));
+
+        // ローンの返済
+        expense.car_expense += cars.iter().zip(&car_loan_schedules)
+            .map(|(car, schedule)| car.estimate_loan_payment(year, schedule.as_ref()))
+            .sum::<u32>();
+        expense.house_expense += houses.iter().zip(house_loan_schedules)
+            .map(|(house, schedule)| house.estimate_loan_payment(year, schedule.as_ref()))
+            .sum::<u32>();
+
+        expenses.push(expense);
     };
 
     expenses
 }
 
+// 期間全体の支出の合計
+pub fn lifetime_total(expenses: &[FamilyExpense]) -> u64 {
+    expenses.iter().map(|e| e.total() as u64).sum()
+}
+
+// (年, その年までの支出の累計)
+pub fn cumulative_totals(expenses: &[FamilyExpense]) -> Vec<(u16, u64)> {
+    expenses.iter().scan(0, |sum, e| {
+        *sum += e.total() as u64;
+        Some((e.year, *sum))
+    }).collect()
+}
+
+// 期間全体の項目ごとの合計
+pub fn category_totals(expenses: &[FamilyExpense]) -> Vec<(ExpenseCategory, u64)> {
+    ExpenseCategory::ALL.into_iter().map(|category| {
+        (category, expenses.iter().map(|e| e.amount(category) as u64).sum())
+    }).collect()
+}
+
+// 期間全体の支出に対する項目ごとの割合
+pub fn category_shares(expenses: &[FamilyExpense]) -> Vec<(ExpenseCategory, f64)> {
+    let total = lifetime_total(expenses);
+    category_totals(expenses).into_iter().map(|(category, amount)| {
+        let share = if total == 0 { 0.0 } else { amount as f64 / total as f64 };
+        (category, share)
+    }).collect()
+}
+
+// 一年分の収支
+#[derive(Debug)]
+pub struct FamilyCashFlow {
+    pub year: u16,
+    pub income: FamilyIncome,
+    pub taxes: Vec<PersonTax>, // 税金と社会保険料
+    pub benefits: FamilyBenefits, // 児童手当などの給付、学費の支援
+    pub mortgage_tax_credit: u32, // 住宅ローン控除で戻る所得税、住民税
+    pub car_resale: u32, // 車の売却、下取りの金額
+    pub deposit_refund: u32, // 賃貸の退去時に戻る敷金
+    pub expense: FamilyExpense,
+    pub balance: i64, // 収入 - 税金、社会保険料 + 給付 + 住宅ローン控除 + 車の売却 + 敷金の返金 - 支出
+}
+
+impl FamilyCashFlow {
+    // 住宅ローン控除と敷金の返金を差し引いた住居費
+    pub fn net_housing_expense(&self) -> i64 {
+        self.expense.house_expense as i64 - self.mortgage_tax_credit as i64 - self.deposit_refund as i64
+    }
+
+    // 売却、下取りの金額を差し引いた車の費用
+    pub fn net_car_expense(&self) -> i64 {
+        self.expense.car_expense as i64 - self.car_resale as i64
+    }
+}
+
+pub fn estimate_family_cash_flows(scenario: &Scenario) -> Vec<FamilyCashFlow> {
+    let Scenario { start_year, houses, assumptions, .. } = scenario;
+    let people = scenario.all_people();
+    let cars = scenario.all_cars();
+    let yearly_taxes = estimate_yearly_taxes(scenario, &people);
+    let house_loan_schedules: Vec<Option<LoanSchedule>> = houses.iter().map(|house| house.loan_schedule()).collect();
+    let incomes: Vec<(FamilyIncome, FamilyBenefits, u32, u32, u32)> = (*start_year..).zip(&yearly_taxes)
+        .map(|(year, taxes)| (
+            income::estimate_family_income(&people, year, assumptions),
+            benefit::estimate_family_benefits(&people, year, assumptions, taxes),
+            houses.iter().zip(&house_loan_schedules)
+                .map(|(house, schedule)| house.estimate_mortgage_deduction(year, schedule.as_ref()))
+                .sum::<u32>(),
+            assumptions.inflation.apply(ExpenseCategory::Car, year, cars.iter().map(|car| car.estimate_resale_value(year)).sum()),
+            houses.iter().map(|house| house.estimate_deposit_refund(year, &assumptions.inflation)).sum::<u32>(),
+        ))
+        .collect();
+    let expenses = estimate_family_expenses_with_taxes(scenario, &people, &cars, &house_loan_schedules, &yearly_taxes);
+
+    incomes.into_iter().zip(yearly_taxes).zip(expenses).zip(*start_year..).map(|((((income, benefits, mortgage_deduction, car_resale, deposit_refund), taxes), expense), year)| {
+        let tax_total: u32 = taxes.iter().map(|tax| tax.total()).sum();
+        let mortgage_tax_credit = tax::estimate_mortgage_tax_credit(&taxes, mortgage_deduction);
+        let balance = income.total() as i64 - tax_total as i64 + benefits.total() as i64 + mortgage_tax_credit as i64 + car_resale as i64 + deposit_refund as i64 - expense.total() as i64;
+        FamilyCashFlow {
+            year,
+            income,
+            taxes,
+            benefits,
+            mortgage_tax_credit,
+            car_resale,
+            deposit_refund,
+            expense,
+            balance,
+        }
+    }).collect()
+}
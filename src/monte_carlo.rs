@@ -0,0 +1,212 @@
+use crate::{Car, House, HouseKind, Person, Pet, estimate_family_cash_flows};
+use crate::asset::{Savings, simulate_assets};
+use crate::income::RaiseCurve;
+use crate::assumptions::Assumptions;
+use crate::inflation::InflationModel;
+use crate::scenario::Scenario;
+use crate::transport::Transport;
+
+// 再現性のためにシードを指定できる乱数（SplitMix64）
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // [0, 1) の一様乱数
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Distribution {
+    Fixed(f64),
+    Uniform {
+        min: f64,
+        max: f64,
+    },
+    Normal {
+        mean: f64,
+        std_dev: f64,
+    },
+}
+
+impl Distribution {
+    pub fn sample(&self, random: &mut Random) -> f64 {
+        match self {
+            Self::Fixed(value) => *value,
+            Self::Uniform { min, max } => min + (max - min) * random.next_f64(),
+            Self::Normal { mean, std_dev } => {
+                // Box-Muller 法
+                let u1 = 1.0 - random.next_f64();
+                let u2 = random.next_f64();
+                let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+                mean + std_dev * z
+            },
+        }
+    }
+}
+
+// 不確かな入力の分布（試行ごとに一つの値をサンプリングして、期間中ずっと使う）
+#[derive(Debug, Clone)]
+pub struct Uncertainty {
+    pub inflation_rate: Distribution, // 物価上昇率（全項目一律）
+    pub investment_yield: Distribution, // NISA, iDeCo の利回り
+    pub salary_growth: Distribution, // 昇給率に加える差分
+    pub loan_interest_rate: Distribution, // ローン金利に加える差分
+    pub lifespan_years: Distribution, // 大人それぞれの寿命
+}
+
+#[derive(Debug)]
+pub struct PercentileBand {
+    pub p5: i64,
+    pub p50: i64,
+    pub p95: i64,
+}
+
+impl PercentileBand {
+    fn new(values: &mut [i64]) -> Self {
+        values.sort_unstable();
+        let percentile = |p: f64| values[((values.len() - 1) as f64 * p).round() as usize];
+        Self {
+            p5: percentile(0.05),
+            p50: percentile(0.5),
+            p95: percentile(0.95),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct YearlyPercentiles {
+    pub year: u16,
+    pub expense: PercentileBand, // 支出の合計
+    pub assets: PercentileBand, // 年末の資産の合計
+}
+
+#[derive(Debug)]
+pub struct MonteCarloResult {
+    pub yearly_percentiles: Vec<YearlyPercentiles>,
+    pub negative_probability: f64, // 期間中に資産が足りなくなる試行の割合
+}
+
+fn add_to_loan_rates(cars: &mut [Car], houses: &mut [House], delta: f64) {
+    let loans = cars.iter_mut().filter_map(|car| car.loan.as_mut())
+        .chain(houses.iter_mut().filter_map(|house| match &mut house.kind {
+            HouseKind::Own { loan, .. } => loan.as_mut(),
+            _ => None,
+        }));
+    for loan in loans {
+        loan.interest_rate = (loan.interest_rate + delta).max(0.0);
+        for (_, rate) in loan.rate_changes.iter_mut() {
+            *rate = (*rate + delta).max(0.0);
+        }
+    }
+}
+
+fn add_to_raise_curve(curve: &mut RaiseCurve, delta: f64) {
+    match curve {
+        RaiseCurve::Constant(rate) => *rate += delta,
+        RaiseCurve::ByAge(rates) => for (_, rate) in rates.iter_mut() {
+            *rate += delta;
+        },
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MonteCarlo {
+    pub iterations: u32,
+    pub seed: u64,
+    pub uncertainty: Uncertainty,
+    pub assumptions: Assumptions, // サンプリングしない値はこの前提を使う
+}
+
+impl MonteCarlo {
+    #[allow(clippy::too_many_arguments)]
+    pub fn run(&self, people: &[Person], cars: &[Car], transports: &[Transport], houses: &[House], pets: &[Pet], savings: &Savings, start_year: u16, years: u8) -> MonteCarloResult {
+        let uncertainty = &self.uncertainty;
+        let iterations = self.iterations;
+        let mut random = Random::new(self.seed);
+        let mut expenses: Vec<Vec<i64>> = vec![Vec::new(); years as usize];
+        let mut assets: Vec<Vec<i64>> = vec![Vec::new(); years as usize];
+        let mut num_negative = 0;
+
+        for _ in 0..iterations {
+            let mut assumptions = self.assumptions.clone();
+            assumptions.inflation.model = InflationModel::Flat(uncertainty.inflation_rate.sample(&mut random));
+
+            let investment_yield = uncertainty.investment_yield.sample(&mut random);
+            let mut savings = savings.clone();
+            for account in [savings.nisa.as_mut(), savings.ideco.as_mut()].into_iter().flatten() {
+                account.expected_yield = investment_yield;
+            }
+
+            let salary_growth = uncertainty.salary_growth.sample(&mut random);
+            let mut people = people.to_vec();
+            for person in people.iter_mut() {
+                if let Some(income) = person.income.as_mut() {
+                    add_to_raise_curve(&mut income.raise_curve, salary_growth);
+                }
+                if !person.is_child {
+                    let lifespan_years = uncertainty.lifespan_years.sample(&mut random);
+                    person.lifespan_years = Some(lifespan_years.round().clamp(0.0, u8::MAX as f64) as u8);
+                }
+            }
+
+            let mut cars = cars.to_vec();
+            let mut houses = houses.to_vec();
+            add_to_loan_rates(&mut cars, &mut houses, uncertainty.loan_interest_rate.sample(&mut random));
+
+            let scenario = Scenario {
+                start_year,
+                years,
+                people,
+                planned_children: Vec::new(),
+                cars,
+                car_replacements: Vec::new(),
+                transports: transports.to_vec(),
+                houses,
+                pets: pets.to_vec(),
+                rent_vs_buy: None,
+                assumptions,
+            };
+            let cash_flows = estimate_family_cash_flows(&scenario);
+            let simulation = simulate_assets(&cash_flows, &savings);
+            if simulation.first_negative_year.is_some() {
+                num_negative += 1;
+            }
+            for (i, (cash_flow, yearly_assets)) in cash_flows.iter().zip(&simulation.yearly_assets).enumerate() {
+                expenses[i].push(cash_flow.expense.total() as i64);
+                assets[i].push(yearly_assets.total());
+            }
+        }
+
+        let yearly_percentiles = if iterations == 0 {
+            Vec::new()
+        } else {
+            expenses.iter_mut().zip(assets.iter_mut()).zip(start_year..).map(|((expenses, assets), year)| {
+                YearlyPercentiles {
+                    year,
+                    expense: PercentileBand::new(expenses),
+                    assets: PercentileBand::new(assets),
+                }
+            }).collect()
+        };
+
+        MonteCarloResult {
+            yearly_percentiles,
+            negative_probability: num_negative as f64 / iterations.max(1) as f64,
+        }
+    }
+}
@@ -0,0 +1,235 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::YearlyLoan;
+
+// 持ち家の維持費と購入時の諸費用
+
+// 固定資産税と都市計画税（23 区）
+// https://www.tax.metro.tokyo.lg.jp/kazei/kotei_tosi.html
+const PROPERTY_TAX_RATE: f64 = 0.014;
+const CITY_PLANNING_TAX_RATE: f64 = 0.003;
+
+// 小規模住宅用地（200㎡ 以下）の課税標準の特例（都市計画税は都独自の軽減を含む）
+const SMALL_RESIDENTIAL_LAND_PROPERTY_TAX_RATIO: f64 = 1.0 / 6.0;
+const SMALL_RESIDENTIAL_LAND_CITY_PLANNING_TAX_RATIO: f64 = 1.0 / 6.0;
+
+// 新築住宅の建物の固定資産税の減額（戸建ては 3 年、マンションは 5 年、1/2）
+const NEW_HOUSE_REDUCTION_RATIO: f64 = 0.5;
+const NEW_DETACHED_HOUSE_REDUCTION_YEARS: u16 = 3;
+const NEW_CONDOMINIUM_REDUCTION_YEARS: u16 = 5;
+
+// 建物の評価額の経年減点補正率の下限
+const MIN_BUILDING_VALUE_RATIO: f64 = 0.2;
+
+// 仲介手数料（400 万円を超える部分の速算式、消費税込み）
+const BROKERAGE_FEE_RATE: f64 = 0.03;
+const BROKERAGE_FEE_ADDITION: f64 = 60000.0;
+const CONSUMPTION_TAX_RATE: f64 = 0.1;
+
+// 登録免許税（土地の売買 1.5%、住宅用家屋の軽減税率、抵当権の設定 0.1%）と司法書士の報酬
+// https://www.nta.go.jp/taxes/shiraberu/taxanswer/inshi/7191.htm
+const LAND_REGISTRATION_TAX_RATE: f64 = 0.015;
+const NEW_BUILDING_REGISTRATION_TAX_RATE: f64 = 0.0015;
+const USED_BUILDING_REGISTRATION_TAX_RATE: f64 = 0.003;
+const MORTGAGE_REGISTRATION_TAX_RATE: f64 = 0.001;
+const JUDICIAL_SCRIVENER_FEE: u32 = 100000;
+
+// 不動産取得税（住宅は 3%、宅地は課税標準が 1/2、新築住宅は 1,200 万円の控除、宅地は最低 45,000 円の減額）
+// https://www.tax.metro.tokyo.lg.jp/kazei/fudosan.html
+const REAL_ESTATE_ACQUISITION_TAX_RATE: f64 = 0.03;
+const RESIDENTIAL_LAND_ACQUISITION_TAX_BASE_RATIO: f64 = 0.5;
+const RESIDENTIAL_LAND_ACQUISITION_TAX_REDUCTION: u32 = 45000;
+const NEW_HOUSE_ACQUISITION_TAX_DEDUCTION: u32 = 12000000;
+
+// 住宅ローンの融資手数料（借入額に対する定率型）
+const LOAN_ORIGINATION_FEE_RATE: f64 = 0.022;
+
+// 住宅ローン控除（2024、2025 年入居、年末残高の 0.7%、新築は 13 年、中古は 10 年）
+// 子育て世帯、若者夫婦世帯の借入限度額の上乗せは考えない
+// https://www.nta.go.jp/taxes/shiraberu/taxanswer/shotoku/1211-1.htm
+const MORTGAGE_DEDUCTION_RATE: f64 = 0.007;
+const NEW_HOUSE_MORTGAGE_DEDUCTION_YEARS: u16 = 13;
+const USED_HOUSE_MORTGAGE_DEDUCTION_YEARS: u16 = 10;
+
+// 住宅の省エネ性能
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum EnergyClass {
+    LongLife, // 認定長期優良住宅、認定低炭素住宅
+    Zeh, // ZEH 水準省エネ住宅
+    EnergyEfficient, // 省エネ基準適合住宅
+    #[default]
+    Other, // その他の住宅
+}
+
+impl EnergyClass {
+    // 住宅ローン控除の借入限度額
+    pub fn mortgage_deduction_balance_limit(&self, is_new: bool) -> u32 {
+        match (self, is_new) {
+            (EnergyClass::LongLife, true) => 45000000,
+            (EnergyClass::Zeh, true) => 35000000,
+            (EnergyClass::EnergyEfficient, true) => 30000000,
+            (EnergyClass::Other, true) => 0, // 2024 年以降に建築確認を受けた新築は対象外
+            (EnergyClass::Other, false) => 20000000,
+            (_, false) => 30000000,
+        }
+    }
+}
+
+// 住宅ローン控除の控除額（税額による上限の前）
+pub fn estimate_mortgage_deduction(energy_class: EnergyClass, is_new: bool, start_year: u16, year: u16, year_end_balance: u32) -> u32 {
+    let deduction_years = if is_new {
+        NEW_HOUSE_MORTGAGE_DEDUCTION_YEARS
+    } else {
+        USED_HOUSE_MORTGAGE_DEDUCTION_YEARS
+    };
+    if year < start_year || start_year + deduction_years <= year {
+        return 0;
+    }
+    let balance = year_end_balance.min(energy_class.mortgage_deduction_balance_limit(is_new));
+    (balance as f64 * MORTGAGE_DEDUCTION_RATE) as u32
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type", rename_all = "snake_case"))]
+pub enum BuildingType {
+    Condominium {
+        monthly_management_fee: u32, // 管理費の月額
+        repair_reserve_steps: Vec<(u16, u32)>, // 修繕積立金の (購入からこの年数から, 月額) を昇順に並べたもの
+    },
+    DetachedHouse {
+        major_repair_interval_years: u16, // 外壁、屋根、水回りなどの大規模修繕の間隔（築年数）
+        major_repair_expense: u32, // 一回の大規模修繕の費用
+    },
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OwnedProperty {
+    pub price: u32, // 購入価格（税込）
+    pub land_value: u32, // 土地の固定資産税評価額
+    pub building_value: u32, // 購入時の建物の固定資産税評価額
+    pub built_year: u16, // 建築年（購入年と同じなら新築）
+    pub building_type: BuildingType,
+    pub annual_insurance_fee: u32, // 火災保険、地震保険の年額
+    pub uses_broker: bool, // 仲介で購入するか（新築の売主から買う場合は false）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub energy_class: EnergyClass,
+}
+
+impl OwnedProperty {
+    fn is_new(&self, start_year: u16) -> bool {
+        start_year <= self.built_year
+    }
+
+    // 建物の法定耐用年数（木造 22 年、鉄筋コンクリート 47 年）
+    fn building_useful_life(&self) -> u16 {
+        match self.building_type {
+            BuildingType::Condominium { .. } => 47,
+            BuildingType::DetachedHouse { .. } => 22,
+        }
+    }
+
+    // 築年数による建物の評価額（下限まで直線で下がる）
+    pub fn estimate_building_value(&self, start_year: u16, year: u16) -> u32 {
+        let useful_life = self.building_useful_life() as f64;
+        let ratio_at = |year: u16| {
+            let age = year.saturating_sub(self.built_year) as f64;
+            (1.0 - (1.0 - MIN_BUILDING_VALUE_RATIO) * age / useful_life).max(MIN_BUILDING_VALUE_RATIO)
+        };
+        (self.building_value as f64 * ratio_at(year) / ratio_at(start_year)) as u32
+    }
+
+    // 固定資産税と都市計画税
+    pub fn estimate_property_tax(&self, start_year: u16, year: u16) -> u32 {
+        let land_tax = self.land_value as f64 * (
+            PROPERTY_TAX_RATE * SMALL_RESIDENTIAL_LAND_PROPERTY_TAX_RATIO +
+            CITY_PLANNING_TAX_RATE * SMALL_RESIDENTIAL_LAND_CITY_PLANNING_TAX_RATIO
+        );
+
+        let building_value = self.estimate_building_value(start_year, year) as f64;
+        let reduction_years = match self.building_type {
+            BuildingType::Condominium { .. } => NEW_CONDOMINIUM_REDUCTION_YEARS,
+            BuildingType::DetachedHouse { .. } => NEW_DETACHED_HOUSE_REDUCTION_YEARS,
+        };
+        let property_tax_ratio = if year < self.built_year + reduction_years {
+            NEW_HOUSE_REDUCTION_RATIO
+        } else {
+            1.0
+        };
+        let building_tax = building_value * (PROPERTY_TAX_RATE * property_tax_ratio + CITY_PLANNING_TAX_RATE);
+
+        (land_tax + building_tax) as u32
+    }
+
+    // 管理費、修繕積立金、戸建ての大規模修繕
+    pub fn estimate_maintenance_expense(&self, start_year: u16, year: u16) -> u32 {
+        match &self.building_type {
+            BuildingType::Condominium { monthly_management_fee, repair_reserve_steps } => {
+                let years = year - start_year;
+                let monthly_repair_reserve = repair_reserve_steps.iter()
+                    .take_while(|(from_years, _)| *from_years <= years)
+                    .last()
+                    .map_or(0, |(_, amount)| *amount);
+                (monthly_management_fee + monthly_repair_reserve) * 12
+            },
+            BuildingType::DetachedHouse { major_repair_interval_years, major_repair_expense } => {
+                let age = year.saturating_sub(self.built_year);
+                if 0 < age && 0 < *major_repair_interval_years && age.is_multiple_of(*major_repair_interval_years) {
+                    *major_repair_expense
+                } else {
+                    0
+                }
+            },
+        }
+    }
+
+    // 毎年の維持費
+    pub fn estimate_annual_expense(&self, start_year: u16, year: u16) -> u32 {
+        self.estimate_property_tax(start_year, year) +
+            self.estimate_maintenance_expense(start_year, year) +
+            self.annual_insurance_fee
+    }
+
+    // 住宅ローン控除の控除額（税額による上限の前）
+    pub fn estimate_mortgage_deduction(&self, start_year: u16, year: u16, year_end_balance: u32) -> u32 {
+        estimate_mortgage_deduction(self.energy_class, self.is_new(start_year), start_year, year, year_end_balance)
+    }
+
+    // 仲介手数料、登記費用、不動産取得税、ローンの融資手数料
+    pub fn estimate_acquisition_expense(&self, start_year: u16, loan: Option<&YearlyLoan>) -> u32 {
+        let brokerage_fee = if self.uses_broker {
+            ((self.price as f64 * BROKERAGE_FEE_RATE + BROKERAGE_FEE_ADDITION) * (1.0 + CONSUMPTION_TAX_RATE)) as u32
+        } else {
+            0
+        };
+
+        let is_new = self.is_new(start_year);
+        let building_registration_tax_rate = if is_new {
+            NEW_BUILDING_REGISTRATION_TAX_RATE
+        } else {
+            USED_BUILDING_REGISTRATION_TAX_RATE
+        };
+        let loan_amount = loan.map_or(0, |loan| loan.amount);
+        let registration_expense = (
+            self.land_value as f64 * LAND_REGISTRATION_TAX_RATE +
+            self.building_value as f64 * building_registration_tax_rate +
+            loan_amount as f64 * MORTGAGE_REGISTRATION_TAX_RATE
+        ) as u32 + JUDICIAL_SCRIVENER_FEE;
+
+        let land_acquisition_tax = ((self.land_value as f64 * RESIDENTIAL_LAND_ACQUISITION_TAX_BASE_RATIO * REAL_ESTATE_ACQUISITION_TAX_RATE) as u32)
+            .saturating_sub(RESIDENTIAL_LAND_ACQUISITION_TAX_REDUCTION);
+        // 中古住宅の築年による控除は考えない
+        let building_acquisition_tax_base = if is_new {
+            self.building_value.saturating_sub(NEW_HOUSE_ACQUISITION_TAX_DEDUCTION)
+        } else {
+            self.building_value
+        };
+        let acquisition_tax = land_acquisition_tax + (building_acquisition_tax_base as f64 * REAL_ESTATE_ACQUISITION_TAX_RATE) as u32;
+
+        let loan_origination_fee = (loan_amount as f64 * LOAN_ORIGINATION_FEE_RATE) as u32;
+
+        brokerage_fee + registration_expense + acquisition_tax + loan_origination_fee
+    }
+}
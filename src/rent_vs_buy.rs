@@ -0,0 +1,210 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ExpenseCategory, House, HouseKind, LoanSchedule};
+use crate::scenario::Scenario;
+use crate::tax;
+
+// 賃貸と持ち家の比較
+// 持ち家は家の価値からローンの残高を引いた分（純資産）を費用から差し引いて比べる
+// 持ち家は end_year に売って、売った金額からローンの残高を返した残りを費用から差し引く
+
+// 土地と建物の評価額が分からない場合の購入価格に対する土地の割合
+const DEFAULT_LAND_SHARE: f64 = 0.5;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RentVsBuy {
+    pub rental: House,
+    pub owned: House,
+    pub land_appreciation_rate: f64, // 土地の価格の年率の変化
+    pub building_appreciation_rate: f64, // 建物の価格の年率の変化（古くなると下がるので普通はマイナス）
+}
+
+// 一年分の比較（金額は start_year からの累計）
+#[derive(Debug)]
+pub struct RentVsBuyYear {
+    pub year: u16,
+    pub rental_outlay: u64, // 賃貸の支払いの累計
+    pub owned_outlay: u64, // 持ち家の支払いの累計（頭金、諸費用、ローンの返済を含む）
+    pub rental_net_cost: i64, // 賃貸の支払いから戻った敷金を引いた累計
+    pub owned_net_cost: i64, // 持ち家の支払いから住宅ローン控除と売った時に手元に残る金額を引いた累計
+    pub remaining_loan_balance: u32, // 年末のローンの残高（売った後は 0）
+    pub property_value: u32, // 家の価値の見積もり（売った後は 0）
+}
+
+impl RentVsBuyYear {
+    // 家の価値からローンの残高を引いた純資産
+    pub fn equity(&self) -> i64 {
+        self.property_value as i64 - self.remaining_loan_balance as i64
+    }
+
+    // 純資産を差し引いた持ち家の実質的な費用
+    pub fn owned_economic_cost(&self) -> i64 {
+        self.owned_net_cost - self.equity()
+    }
+}
+
+// 比べられない場合
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RentVsBuyError {
+    NotRental, // rental の kind が HouseKind::Rental ではない
+    NotOwned, // owned の kind が HouseKind::Own ではない
+    // 賃貸と持ち家の住む期間 (start_year, end_year) が違う（賃貸の end_year は定期借家で退去する年）
+    SpanMismatch {
+        rental: (u16, u16),
+        owned: (u16, u16),
+    },
+    // 買う年か売る年（end_year）が scenario の期間 (start_year, start_year + years) の外で、費用に入らない
+    OutsideScenario {
+        owned: (u16, u16),
+        scenario: (u16, u16),
+    },
+}
+
+impl fmt::Display for RentVsBuyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotRental => write!(f, "rental must be a rental house"),
+            Self::NotOwned => write!(f, "owned must be an owned house"),
+            Self::SpanMismatch { rental, owned } => write!(
+                f,
+                "rental ({} - {}) and owned ({} - {}) must cover the same years",
+                rental.0, rental.1, owned.0, owned.1,
+            ),
+            Self::OutsideScenario { owned, scenario } => write!(
+                f,
+                "owned ({} - {}) must be bought and sold within the scenario years ({} - {})",
+                owned.0, owned.1, scenario.0, scenario.1 - 1,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RentVsBuyError {}
+
+#[derive(Debug)]
+pub struct RentVsBuyComparison {
+    pub yearly: Vec<RentVsBuyYear>,
+    pub break_even_year: Option<u16>, // 持ち家の実質的な費用が賃貸以下になる最初の年
+}
+
+impl RentVsBuy {
+    // 家の価値（土地と建物を別々の率で変化させる、end_year に売った後は 0）
+    pub fn estimate_property_value(&self, year: u16) -> u32 {
+        let house = &self.owned;
+        if year < house.start_year || house.end_year <= year {
+            return 0;
+        }
+        self.estimate_value_after(year - house.start_year)
+    }
+
+    // end_year に売る金額
+    pub fn estimate_sale_price(&self) -> u32 {
+        self.estimate_value_after(self.owned.end_year.saturating_sub(self.owned.start_year))
+    }
+
+    // 買ってから years 年後の家の価値
+    fn estimate_value_after(&self, years: u16) -> u32 {
+        let HouseKind::Own { down_payment, loan, property } = &self.owned.kind else {
+            return 0;
+        };
+        let (price, land_share) = match property {
+            Some(property) => {
+                let assessed_value = property.land_value as f64 + property.building_value as f64;
+                let land_share = if assessed_value == 0.0 { DEFAULT_LAND_SHARE } else { property.land_value as f64 / assessed_value };
+                (property.price as f64, land_share)
+            },
+            None => ((down_payment + loan.as_ref().map_or(0, |loan| loan.amount)) as f64, DEFAULT_LAND_SHARE),
+        };
+        let years = years as i32;
+        let land_value = price * land_share * (1.0 + self.land_appreciation_rate).powi(years);
+        let building_value = price * (1.0 - land_share) * (1.0 + self.building_appreciation_rate).max(0.0).powi(years);
+        (land_value + building_value) as u32
+    }
+
+    // 年末のローンの残高（end_year に売った時に返すので、その後は 0）
+    fn estimate_remaining_loan_balance(&self, year: u16, schedule: Option<&LoanSchedule>) -> u32 {
+        match schedule {
+            Some(schedule) if self.owned.start_year <= year && year < self.owned.end_year => schedule.remaining_balance(year - self.owned.start_year + 1),
+            _ => 0,
+        }
+    }
+
+    // 金額は scenario.assumptions.inflation による名目値（ローンの返済額は契約時の金額のまま）
+    // 住宅ローン控除は scenario.houses のローンの控除を先に使った残りの税額から引く
+    pub fn compare(&self, scenario: &Scenario) -> Result<RentVsBuyComparison, RentVsBuyError> {
+        if !matches!(self.rental.kind, HouseKind::Rental { .. }) {
+            return Err(RentVsBuyError::NotRental);
+        }
+        if !matches!(self.owned.kind, HouseKind::Own { .. }) {
+            return Err(RentVsBuyError::NotOwned);
+        }
+        let rental_span = (self.rental.start_year, self.rental.move_out_year());
+        let owned_span = (self.owned.start_year, self.owned.end_year);
+        if rental_span != owned_span {
+            return Err(RentVsBuyError::SpanMismatch { rental: rental_span, owned: owned_span });
+        }
+        let scenario_span = (scenario.start_year, scenario.start_year + scenario.years as u16);
+        if owned_span.0 < scenario_span.0 || scenario_span.1 <= owned_span.1 {
+            return Err(RentVsBuyError::OutsideScenario { owned: owned_span, scenario: scenario_span });
+        }
+
+        let people = scenario.all_people();
+        let assumptions = &scenario.assumptions;
+        let rental_loan_schedule = self.rental.loan_schedule();
+        let owned_loan_schedule = self.owned.loan_schedule();
+        let scenario_loan_schedules: Vec<Option<LoanSchedule>> = scenario.houses.iter().map(|house| house.loan_schedule()).collect();
+        let outlay = |house: &House, schedule: Option<&LoanSchedule>, year: u16| {
+            // 売った後のローンの残りは売った金額から返す
+            let loan_payment = if year < house.end_year { house.estimate_loan_payment(year, schedule) } else { 0 };
+            assumptions.inflation.apply(ExpenseCategory::House, year, house.estimate_running_expense(year)) + loan_payment
+        };
+        let sale_proceeds = self.estimate_sale_price() as i64 -
+            owned_loan_schedule.as_ref().map_or(0, |schedule| schedule.remaining_balance(self.owned.end_year - self.owned.start_year)) as i64;
+
+        let mut rental_outlay = 0;
+        let mut owned_outlay = 0;
+        let mut rental_net_cost = 0;
+        let mut owned_net_cost = 0;
+        let mut yearly = Vec::new();
+        for year in scenario.start_year..(scenario.start_year + scenario.years as u16) {
+            let rental_expense = outlay(&self.rental, rental_loan_schedule.as_ref(), year);
+            let owned_expense = outlay(&self.owned, owned_loan_schedule.as_ref(), year);
+            let deposit_refund = self.rental.estimate_deposit_refund(year, &assumptions.inflation);
+            let taxes = tax::estimate_family_taxes(&people, year, assumptions);
+            let scenario_deduction: u32 = scenario.houses.iter().zip(&scenario_loan_schedules)
+                .map(|(house, schedule)| house.estimate_mortgage_deduction(year, schedule.as_ref()))
+                .sum();
+            let owned_deduction = self.owned.estimate_mortgage_deduction(year, owned_loan_schedule.as_ref());
+            let mortgage_tax_credit = tax::estimate_mortgage_tax_credit(&taxes, scenario_deduction + owned_deduction) -
+                tax::estimate_mortgage_tax_credit(&taxes, scenario_deduction);
+
+            rental_outlay += rental_expense as u64;
+            owned_outlay += owned_expense as u64;
+            rental_net_cost += rental_expense as i64 - deposit_refund as i64;
+            owned_net_cost += owned_expense as i64 - mortgage_tax_credit as i64;
+            if year == self.owned.end_year {
+                owned_net_cost -= sale_proceeds;
+            }
+            yearly.push(RentVsBuyYear {
+                year,
+                rental_outlay,
+                owned_outlay,
+                rental_net_cost,
+                owned_net_cost,
+                remaining_loan_balance: self.estimate_remaining_loan_balance(year, owned_loan_schedule.as_ref()),
+                property_value: self.estimate_property_value(year),
+            });
+        }
+
+        let break_even_year = yearly.iter()
+            .filter(|row| self.owned.start_year <= row.year)
+            .find(|row| row.owned_economic_cost() <= row.rental_net_cost)
+            .map(|row| row.year);
+
+        Ok(RentVsBuyComparison { yearly, break_even_year })
+    }
+}
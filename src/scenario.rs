@@ -0,0 +1,102 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Car, CarReplacement, FamilyCashFlow, FamilyExpense, House, Person, Pet, estimate_family_cash_flows, estimate_family_expenses};
+use crate::assumptions::Assumptions;
+use crate::birth::PlannedChild;
+use crate::rent_vs_buy::RentVsBuy;
+use crate::transport::Transport;
+
+// 見積もりに必要な家族の情報一式（設定ファイルから読み込む単位）
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Scenario {
+    pub start_year: u16,
+    pub years: u8,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub people: Vec<Person>,
+    // これから生まれる予定の子供（生まれる順）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub planned_children: Vec<PlannedChild>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cars: Vec<Car>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub car_replacements: Vec<CarReplacement>,
+    // リース、カーシェア、タクシー
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub transports: Vec<Transport>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub houses: Vec<House>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pets: Vec<Pet>,
+    // 賃貸と持ち家の比較（houses とは別に比べる二つの家）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rent_vs_buy: Option<RentVsBuy>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub assumptions: Assumptions,
+}
+
+impl Scenario {
+    #[cfg(feature = "serde")]
+    pub fn from_toml_str(s: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(s)
+    }
+
+    #[cfg(feature = "serde")]
+    pub fn from_json_str(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    // 予定の子供を含めた家族全員
+    pub fn all_people(&self) -> Vec<Person> {
+        let mut people = self.people.clone();
+        people.extend(self.planned_children.iter().map(|child| child.to_person()));
+        people
+    }
+
+    // 買い替えで生成する車を含めた全ての車
+    pub fn all_cars(&self) -> Vec<Car> {
+        let mut cars = self.cars.clone();
+        cars.extend(self.car_replacements.iter().flat_map(|replacement| replacement.cars()));
+        cars
+    }
+
+    // 予定の子供を最初の num_planned_children 人だけにしたもの
+    pub fn with_planned_children(&self, num_planned_children: usize) -> Scenario {
+        let mut scenario = self.clone();
+        scenario.planned_children.truncate(num_planned_children);
+        scenario
+    }
+
+    pub fn estimate_family_expenses(&self) -> Vec<FamilyExpense> {
+        estimate_family_expenses(self)
+    }
+
+    pub fn estimate_family_cash_flows(&self) -> Vec<FamilyCashFlow> {
+        estimate_family_cash_flows(self)
+    }
+
+    // 予定の子供が 0 人から全員までのそれぞれの場合の収支
+    pub fn compare_planned_children(&self) -> Vec<ChildPlanComparison> {
+        (0..=self.planned_children.len()).map(|num_planned_children| {
+            let cash_flows = self.with_planned_children(num_planned_children).estimate_family_cash_flows();
+            ChildPlanComparison {
+                num_planned_children,
+                lifetime_expense: cash_flows.iter().map(|cash_flow| cash_flow.expense.total() as u64).sum(),
+                lifetime_benefits: cash_flows.iter().map(|cash_flow| cash_flow.benefits.total() as u64).sum(),
+                lifetime_balance: cash_flows.iter().map(|cash_flow| cash_flow.balance).sum(),
+                cash_flows,
+            }
+        }).collect()
+    }
+}
+
+// 予定の子供の人数ごとの収支
+#[derive(Debug)]
+pub struct ChildPlanComparison {
+    pub num_planned_children: usize,
+    pub lifetime_expense: u64,
+    pub lifetime_benefits: u64,
+    pub lifetime_balance: i64,
+    pub cash_flows: Vec<FamilyCashFlow>,
+}
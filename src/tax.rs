@@ -0,0 +1,415 @@
+use crate::{LifeStage, Person};
+use crate::assumptions::Assumptions;
+use crate::income::PersonIncome;
+
+// 2025 年度の税制改正後の値を元にしている
+// https://www.nta.go.jp/taxes/shiraberu/taxanswer/shotoku/2260.htm
+// https://www.tax.metro.tokyo.lg.jp/kazei/kojin_ju
+
+// 所得税の速算表 (課税所得の上限, 税率, 控除額)
+const INCOME_TAX_TABLE: [(u32, f64, u32); 7] = [
+    (1949000, 0.05, 0),
+    (3299000, 0.10, 97500),
+    (6949000, 0.20, 427500),
+    (8999000, 0.23, 636000),
+    (17999000, 0.33, 1536000),
+    (39999000, 0.40, 2796000),
+    (u32::MAX, 0.45, 4796000),
+];
+
+// 復興特別所得税（2037 年分まで）
+const RECONSTRUCTION_TAX_RATE: f64 = 0.021;
+const RECONSTRUCTION_TAX_LAST_YEAR: u16 = 2037;
+
+// 住民税（東京 23 区）の所得割（特別区民税 6% + 都民税 4%）
+const RESIDENT_TAX_RATE: f64 = 0.1;
+
+// 住民税の均等割（特別区民税 3000 円 + 都民税 1000 円 + 森林環境税 1000 円）
+const RESIDENT_TAX_PER_CAPITA: u32 = 5000;
+
+// 住民税の所得割のうち特別区民税の割合（6% / 10%）
+const MUNICIPAL_INCOME_LEVY_RATIO: f64 = 0.6;
+
+// 配偶者控除、扶養控除の対象となる合計所得の上限
+const DEPENDENT_INCOME_LIMIT: u32 = 580000;
+
+// 協会けんぽ（東京）の健康保険料率、介護保険料率（労使折半前）
+// https://www.kyoukaikenpo.or.jp/g7/cat330/sb3150/r07/r7ryougakuhyou3gatukara/
+const HEALTH_INSURANCE_RATE: f64 = 0.0991;
+const NURSING_CARE_INSURANCE_RATE: f64 = 0.0159;
+const HEALTH_INSURANCE_MAX_MONTHLY_REMUNERATION: u32 = 1390000;
+const HEALTH_INSURANCE_MAX_ANNUAL_BONUS: u32 = 5730000;
+
+// 厚生年金保険料率（労使折半前）
+const PENSION_INSURANCE_RATE: f64 = 0.183;
+const PENSION_INSURANCE_MAX_MONTHLY_REMUNERATION: u32 = 650000;
+const PENSION_INSURANCE_MAX_BONUS: u32 = 1500000 * 2; // 1 回 150 万円を上限に年 2 回支給とする
+
+// 雇用保険料率（労働者負担分）
+const EMPLOYMENT_INSURANCE_RATE: f64 = 0.0055;
+
+// 退職後の国民健康保険（23 区、医療分 + 支援金分）と後期高齢者医療保険（東京都）
+// https://www.city.shinjuku.lg.jp/kusei/file02_00001.html
+// https://www.tokyo-ikiiki.net/
+const NATIONAL_HEALTH_INSURANCE_RATE: f64 = 0.1149;
+const NATIONAL_HEALTH_INSURANCE_PER_CAPITA: u32 = 47300 + 16800;
+const NATIONAL_HEALTH_INSURANCE_MAX: u32 = 650000 + 240000;
+const LATTER_STAGE_ELDERLY_AGE: u8 = 75;
+const LATTER_STAGE_ELDERLY_INSURANCE_RATE: f64 = 0.0967;
+const LATTER_STAGE_ELDERLY_INSURANCE_PER_CAPITA: u32 = 47300;
+const LATTER_STAGE_ELDERLY_INSURANCE_MAX: u32 = 800000;
+
+// 介護保険料（40 〜 64 歳は健康保険に上乗せ、65 歳以上は区の第 1 号保険料の基準額）
+const NURSING_CARE_INSURANCE_START_AGE: u8 = 40;
+const ELDERLY_NURSING_CARE_INSURANCE_AGE: u8 = 65;
+const ELDERLY_NURSING_CARE_INSURANCE_PREMIUM: u32 = 6600 * 12;
+
+// 保険料の計算で使う所得から引く基礎控除
+const NATIONAL_HEALTH_INSURANCE_BASE_DEDUCTION: u32 = 430000;
+
+// 住宅ローン控除の住民税から引ける上限（所得税の課税総所得金額の 5%、97,500 円まで）
+// https://www.nta.go.jp/taxes/shiraberu/taxanswer/shotoku/1211-1.htm
+const MORTGAGE_RESIDENT_TAX_CREDIT_RATE: f64 = 0.05;
+const MORTGAGE_RESIDENT_TAX_CREDIT_LIMIT: u32 = 97500;
+
+// 社会保険料
+#[derive(Debug, Default)]
+pub struct SocialInsurance {
+    pub health_insurance: u32, // 健康保険 / 国民健康保険 / 後期高齢者医療保険
+    pub nursing_care_insurance: u32, // 介護保険
+    pub pension_insurance: u32, // 厚生年金
+    pub employment_insurance: u32, // 雇用保険
+}
+
+impl SocialInsurance {
+    pub fn total(&self) -> u32 {
+        self.health_insurance +
+            self.nursing_care_insurance +
+            self.pension_insurance +
+            self.employment_insurance
+    }
+}
+
+// 一人分の税金と手取り
+#[derive(Debug)]
+pub struct PersonTax {
+    pub name: String,
+    pub gross_income: u32, // 額面の収入
+    pub social_insurance: SocialInsurance,
+    pub taxable_income: u32, // 所得税の課税総所得金額
+    pub income_tax: u32, // 所得税（復興特別所得税を含む）
+    pub resident_tax: u32, // 住民税（前年の所得に対して課税）
+}
+
+impl PersonTax {
+    pub fn total(&self) -> u32 {
+        self.social_insurance.total() + self.income_tax + self.resident_tax
+    }
+
+    pub fn take_home_pay(&self) -> u32 {
+        self.gross_income.saturating_sub(self.total())
+    }
+
+    // 特別区民税の所得割額（保育料の算定に使う）
+    pub fn municipal_income_levy(&self) -> u32 {
+        (self.resident_tax.saturating_sub(RESIDENT_TAX_PER_CAPITA) as f64 * MUNICIPAL_INCOME_LEVY_RATIO) as u32
+    }
+}
+
+// 世帯の住民税の課税の状況（保育料、修学支援の判定に使う）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HouseholdResidentTax {
+    Exempt, // 住民税非課税
+    PerCapitaOnly, // 均等割のみ課税
+    IncomeLevy(u32), // 所得割課税（世帯の特別区民税の所得割額の合計）
+}
+
+impl HouseholdResidentTax {
+    // 誰も収入のデータがない場合は非課税とは分からないので課税として扱う
+    pub fn new(taxes: &[PersonTax]) -> Self {
+        let municipal_income_levy = taxes.iter().map(|tax| tax.municipal_income_levy()).sum();
+        if 0 < municipal_income_levy || taxes.is_empty() {
+            Self::IncomeLevy(municipal_income_levy)
+        } else if taxes.iter().any(|tax| 0 < tax.resident_tax) {
+            Self::PerCapitaOnly
+        } else {
+            Self::Exempt
+        }
+    }
+}
+
+// 給与所得控除
+pub fn calculate_employment_income_deduction(salary: u32) -> u32 {
+    let salary = salary as f64;
+    let deduction = match salary as u32 {
+        0..=1900000 => 650000.0,
+        1900001..=3600000 => salary * 0.3 + 80000.0,
+        3600001..=6600000 => salary * 0.2 + 440000.0,
+        6600001..=8500000 => salary * 0.1 + 1100000.0,
+        _ => 1950000.0,
+    };
+    (deduction as u32).min(salary as u32)
+}
+
+// 公的年金等控除（65 歳以上、年金以外の所得が 1000 万円以下）
+pub fn calculate_public_pension_deduction(pension: u32) -> u32 {
+    let pension = pension as f64;
+    let deduction = match pension as u32 {
+        0..=3299999 => 1100000.0,
+        3300000..=4099999 => pension * 0.25 + 275000.0,
+        4100000..=7699999 => pension * 0.15 + 685000.0,
+        7700000..=9999999 => pension * 0.05 + 1455000.0,
+        _ => 1955000.0,
+    };
+    (deduction as u32).min(pension as u32)
+}
+
+// 合計所得（給与所得 + 公的年金の雑所得）
+pub fn calculate_total_income(income: &PersonIncome) -> u32 {
+    let salary = income.salary + income.bonus;
+    (salary - calculate_employment_income_deduction(salary)) +
+        (income.pension - calculate_public_pension_deduction(income.pension))
+}
+
+// 基礎控除（所得税）
+fn calculate_income_tax_basic_deduction(total_income: u32) -> u32 {
+    match total_income {
+        0..=1320000 => 950000,
+        1320001..=23500000 => 580000,
+        23500001..=24000000 => 480000,
+        24000001..=24500000 => 320000,
+        24500001..=25000000 => 160000,
+        _ => 0,
+    }
+}
+
+// 基礎控除（住民税）
+fn calculate_resident_tax_basic_deduction(total_income: u32) -> u32 {
+    match total_income {
+        0..=24000000 => 430000,
+        24000001..=24500000 => 290000,
+        24500001..=25000000 => 150000,
+        _ => 0,
+    }
+}
+
+// 配偶者控除 (所得税, 住民税)
+fn calculate_spouse_deduction(total_income: u32, spouse_age: u8) -> (u32, u32) {
+    let (income_tax_deduction, resident_tax_deduction) = if 70 <= spouse_age {
+        (480000, 380000)
+    } else {
+        (380000, 330000)
+    };
+    let ratio = match total_income {
+        0..=9000000 => 3,
+        9000001..=9500000 => 2,
+        9500001..=10000000 => 1,
+        _ => 0,
+    };
+    (income_tax_deduction * ratio / 3, resident_tax_deduction * ratio / 3)
+}
+
+// 扶養控除 (所得税, 住民税)
+fn calculate_dependent_deduction(dependent_age: u8) -> (u32, u32) {
+    match dependent_age {
+        0..=15 => (0, 0),
+        16..=18 => (380000, 330000),
+        19..=22 => (630000, 450000), // 特定扶養親族
+        23..=69 => (380000, 330000),
+        _ => (480000, 380000), // 老人扶養親族
+    }
+}
+
+// 給与にかかる社会保険料（従業員負担分）
+fn calculate_employee_social_insurance(monthly_salary: u32, bonus: u32, age: u8) -> SocialInsurance {
+    let health_remuneration = monthly_salary.min(HEALTH_INSURANCE_MAX_MONTHLY_REMUNERATION) as f64 * 12.0 +
+        bonus.min(HEALTH_INSURANCE_MAX_ANNUAL_BONUS) as f64;
+    let pension_remuneration = monthly_salary.min(PENSION_INSURANCE_MAX_MONTHLY_REMUNERATION) as f64 * 12.0 +
+        bonus.min(PENSION_INSURANCE_MAX_BONUS) as f64;
+    let nursing_care_insurance = if NURSING_CARE_INSURANCE_START_AGE <= age {
+        (health_remuneration * NURSING_CARE_INSURANCE_RATE / 2.0) as u32
+    } else {
+        0
+    };
+    SocialInsurance {
+        health_insurance: (health_remuneration * HEALTH_INSURANCE_RATE / 2.0) as u32,
+        nursing_care_insurance,
+        pension_insurance: (pension_remuneration * PENSION_INSURANCE_RATE / 2.0) as u32,
+        employment_insurance: ((monthly_salary * 12 + bonus) as f64 * EMPLOYMENT_INSURANCE_RATE) as u32,
+    }
+}
+
+// 退職後の社会保険料（国民健康保険または後期高齢者医療保険と介護保険）
+fn calculate_retiree_social_insurance(total_income: u32, age: u8) -> SocialInsurance {
+    let base = total_income.saturating_sub(NATIONAL_HEALTH_INSURANCE_BASE_DEDUCTION) as f64;
+    let health_insurance = if LATTER_STAGE_ELDERLY_AGE <= age {
+        ((base * LATTER_STAGE_ELDERLY_INSURANCE_RATE) as u32 + LATTER_STAGE_ELDERLY_INSURANCE_PER_CAPITA)
+            .min(LATTER_STAGE_ELDERLY_INSURANCE_MAX)
+    } else {
+        ((base * NATIONAL_HEALTH_INSURANCE_RATE) as u32 + NATIONAL_HEALTH_INSURANCE_PER_CAPITA)
+            .min(NATIONAL_HEALTH_INSURANCE_MAX)
+    };
+    let nursing_care_insurance = if ELDERLY_NURSING_CARE_INSURANCE_AGE <= age {
+        ELDERLY_NURSING_CARE_INSURANCE_PREMIUM
+    } else {
+        0
+    };
+    SocialInsurance {
+        health_insurance,
+        nursing_care_insurance,
+        ..Default::default()
+    }
+}
+
+// 速算表による所得税
+fn calculate_income_tax(taxable_income: u32, year: u16) -> u32 {
+    let taxable_income = taxable_income / 1000 * 1000;
+    let (_, rate, deduction) = INCOME_TAX_TABLE.iter()
+        .find(|(limit, _, _)| taxable_income <= *limit)
+        .unwrap();
+    let mut tax = (taxable_income as f64 * rate) as u32 - deduction;
+    if year <= RECONSTRUCTION_TAX_LAST_YEAR {
+        tax += (tax as f64 * RECONSTRUCTION_TAX_RATE) as u32;
+    }
+    tax / 100 * 100
+}
+
+// その年の所得と控除
+struct Assessment {
+    name: String,
+    gross_income: u32,
+    total_income: u32, // 合計所得
+    social_insurance: SocialInsurance,
+    income_tax_deduction: u32, // 所得控除の合計（所得税）
+    resident_tax_deduction: u32, // 所得控除の合計（住民税）
+    personal_deduction_difference: u32, // 人的控除の差（住民税の調整控除に使う）
+    num_dependents: u32, // 同一生計配偶者と扶養親族の数
+}
+
+fn assess(people: &[Person], year: u16, assumptions: &Assumptions) -> Vec<Option<Assessment>> {
+    let mut assessments: Vec<Option<Assessment>> = people.iter().map(|person| {
+        let income_setting = person.income.as_ref()?;
+        let age = person.get_grade_age(year, assumptions)?;
+        let income = income_setting.estimate_income(person, year, assumptions);
+        let total_income = calculate_total_income(&income);
+        let social_insurance = if income.salary > 0 {
+            calculate_employee_social_insurance(income.salary / 12, income.bonus, age)
+        } else {
+            calculate_retiree_social_insurance(total_income, age)
+        };
+        Some(Assessment {
+            name: person.name.clone(),
+            gross_income: income.total(),
+            total_income,
+            income_tax_deduction: calculate_income_tax_basic_deduction(total_income) + social_insurance.total(),
+            resident_tax_deduction: calculate_resident_tax_basic_deduction(total_income) + social_insurance.total(),
+            social_insurance,
+            personal_deduction_difference: 50000,
+            num_dependents: 0,
+        })
+    }).collect();
+
+    // 配偶者控除と扶養控除は所得の一番多い大人が受ける
+    let Some(taxpayer_index) = people.iter().enumerate()
+        .filter(|(_, person)| !person.is_child)
+        .filter_map(|(i, _)| assessments[i].as_ref().map(|a| (i, a.total_income)))
+        .max_by_key(|(_, total_income)| *total_income)
+        .map(|(i, _)| i) else {
+        return assessments;
+    };
+
+    let mut income_tax_deduction = 0;
+    let mut resident_tax_deduction = 0;
+    let mut personal_deduction_difference = 0;
+    let mut num_dependents = 0;
+    for (i, person) in people.iter().enumerate() {
+        if i == taxpayer_index {
+            continue;
+        }
+        let Some(age) = person.get_grade_age(year, assumptions) else {
+            continue;
+        };
+        let total_income = assessments[i].as_ref().map(|a| a.total_income).unwrap_or(0);
+        if DEPENDENT_INCOME_LIMIT < total_income {
+            continue;
+        }
+        let (income_tax, resident_tax) = if person.is_child {
+            // 働いている子供は扶養から外れる
+            if person.get_life_stage(age, assumptions) == LifeStage::Working {
+                continue;
+            }
+            calculate_dependent_deduction(age)
+        } else {
+            let taxpayer_total_income = assessments[taxpayer_index].as_ref().unwrap().total_income;
+            calculate_spouse_deduction(taxpayer_total_income, age)
+        };
+        income_tax_deduction += income_tax;
+        resident_tax_deduction += resident_tax;
+        personal_deduction_difference += income_tax - resident_tax;
+        num_dependents += 1;
+    }
+
+    let taxpayer = assessments[taxpayer_index].as_mut().unwrap();
+    taxpayer.income_tax_deduction += income_tax_deduction;
+    taxpayer.resident_tax_deduction += resident_tax_deduction;
+    taxpayer.personal_deduction_difference += personal_deduction_difference;
+    taxpayer.num_dependents += num_dependents;
+    assessments
+}
+
+// 前年の所得に対する住民税（23 区の非課税限度額と調整控除を考慮）
+fn calculate_resident_tax(assessment: &Assessment) -> u32 {
+    let num_people = assessment.num_dependents + 1;
+    let has_dependents = 0 < assessment.num_dependents;
+
+    let per_capita_limit = if has_dependents { 350000 * num_people + 100000 + 210000 } else { 450000 };
+    if assessment.total_income <= per_capita_limit {
+        return 0;
+    }
+
+    let income_limit = if has_dependents { 350000 * num_people + 100000 + 320000 } else { 450000 };
+    let income_part = if assessment.total_income <= income_limit {
+        0
+    } else {
+        let taxable_income = assessment.total_income.saturating_sub(assessment.resident_tax_deduction) / 1000 * 1000;
+        let difference = assessment.personal_deduction_difference;
+        let adjustment = if taxable_income <= 2000000 {
+            difference.min(taxable_income) / 20
+        } else {
+            difference.saturating_sub(taxable_income - 2000000).max(50000) / 20
+        };
+        ((taxable_income as f64 * RESIDENT_TAX_RATE) as u32).saturating_sub(adjustment) / 100 * 100
+    };
+    income_part + RESIDENT_TAX_PER_CAPITA
+}
+
+// 家族それぞれの税金と社会保険料（収入がある人のみ）
+pub fn estimate_family_taxes(people: &[Person], year: u16, assumptions: &Assumptions) -> Vec<PersonTax> {
+    let previous_assessments = assess(people, year - 1, assumptions);
+    assess(people, year, assumptions).into_iter().zip(previous_assessments).filter_map(|(assessment, previous_assessment)| {
+        let assessment = assessment?;
+        let taxable_income = assessment.total_income.saturating_sub(assessment.income_tax_deduction);
+        Some(PersonTax {
+            name: assessment.name,
+            gross_income: assessment.gross_income,
+            social_insurance: assessment.social_insurance,
+            taxable_income,
+            income_tax: calculate_income_tax(taxable_income, year),
+            resident_tax: previous_assessment.as_ref().map(calculate_resident_tax).unwrap_or(0),
+        })
+    }).collect()
+}
+
+// 住宅ローン控除で実際に戻る税額
+// 所得の一番多い人がローンを借りる想定で、所得税から引ききれない分は住民税から引く
+pub fn estimate_mortgage_tax_credit(taxes: &[PersonTax], deduction: u32) -> u32 {
+    let Some(borrower) = taxes.iter().max_by_key(|tax| tax.gross_income) else {
+        return 0;
+    };
+    let income_tax_credit = deduction.min(borrower.income_tax);
+    let resident_tax_limit = ((borrower.taxable_income as f64 * MORTGAGE_RESIDENT_TAX_CREDIT_RATE) as u32)
+        .min(MORTGAGE_RESIDENT_TAX_CREDIT_LIMIT)
+        .min(borrower.resident_tax.saturating_sub(RESIDENT_TAX_PER_CAPITA));
+    let resident_tax_credit = (deduction - income_tax_credit).min(resident_tax_limit);
+    income_tax_credit + resident_tax_credit
+}
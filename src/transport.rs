@@ -0,0 +1,81 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+// 車を持たない移動手段（リース、カーシェア、タクシー）
+// 車を買う場合は Car を使う
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(tag = "type", rename_all = "snake_case"))]
+pub enum TransportMode {
+    // カーリース（月額に税金、自賠責、車検、メンテナンスを含む）
+    Lease {
+        monthly_fee: u32,
+        annual_mileage_limit_km: u32, // 年間の走行距離の上限
+        expected_annual_km: u32, // 年間の走行距離の予想
+        excess_fee_per_km: u32, // 上限を超えた 1km あたりの精算額
+        annual_optional_insurance_fee: u32, // 任意保険の年額
+        annual_gas_expense: u32, // 年間のガソリン代
+        monthly_parking_fee: u32, // 月極駐車場
+    },
+    // カーシェア（時間料金と距離料金、ガソリン代と保険を含む）
+    CarSharing {
+        monthly_base_fee: u32, // 月額基本料
+        hourly_fee: u32, // 1 時間あたりの料金
+        per_km_fee: u32, // 1km あたりの距離料金
+        expected_monthly_hours: f64, // 月の利用時間の予想
+        expected_monthly_km: f64, // 月の走行距離の予想
+    },
+    // タクシー
+    Taxi {
+        average_fare: u32, // 一回の平均の運賃
+        expected_monthly_rides: f64, // 月の乗車回数の予想
+    },
+}
+
+impl TransportMode {
+    pub fn estimate_annual_expense(&self) -> u32 {
+        match self {
+            TransportMode::Lease {
+                monthly_fee,
+                annual_mileage_limit_km,
+                expected_annual_km,
+                excess_fee_per_km,
+                annual_optional_insurance_fee,
+                annual_gas_expense,
+                monthly_parking_fee,
+            } => {
+                // 走行距離の超過分は契約終了時に精算するが、年割で計上する
+                let excess_fee = expected_annual_km.saturating_sub(*annual_mileage_limit_km) * excess_fee_per_km;
+                (monthly_fee + monthly_parking_fee) * 12 +
+                    excess_fee +
+                    annual_optional_insurance_fee +
+                    annual_gas_expense
+            },
+            TransportMode::CarSharing { monthly_base_fee, hourly_fee, per_km_fee, expected_monthly_hours, expected_monthly_km } => {
+                let monthly_usage_fee = *hourly_fee as f64 * expected_monthly_hours + *per_km_fee as f64 * expected_monthly_km;
+                (monthly_base_fee * 12) + (monthly_usage_fee * 12.0) as u32
+            },
+            TransportMode::Taxi { average_fare, expected_monthly_rides } => {
+                (*average_fare as f64 * expected_monthly_rides * 12.0) as u32
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Transport {
+    pub start_year: u16, // include
+    pub end_year: u16, // not include
+    pub mode: TransportMode,
+}
+
+impl Transport {
+    pub fn estimate_expense(&self, year: u16) -> u32 {
+        if self.start_year <= year && year < self.end_year {
+            self.mode.estimate_annual_expense()
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,252 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Car, Depreciation, YearlyLoan};
+
+// 車の諸元から自動車税（種別割）、重量税、自賠責、車検の費用を求める
+// 車検は新車登録から 3 年後、その後は 2 年ごと
+
+// 自動車税（種別割）の (総排気量の上限, 年額)（2019 年 10 月以降に登録した自家用乗用車）
+const CAR_TYPE_TAX_TABLE: [(u32, u32); 10] = [
+    (1000, 25000),
+    (1500, 30500),
+    (2000, 36000),
+    (2500, 43500),
+    (3000, 50000),
+    (3500, 57000),
+    (4000, 65500),
+    (4500, 75500),
+    (6000, 87000),
+    (u32::MAX, 110000),
+];
+const KEI_CAR_TYPE_TAX: u32 = 10800;
+const OLD_KEI_CAR_TYPE_TAX: u32 = 12900;
+
+// 新車登録から 13 年経過した車の重課（自動車税（種別割）と重量税で同じ境目にする）
+const OLD_CAR_YEARS: u16 = 13;
+const OLD_CAR_TYPE_TAX_RATE: f64 = 1.15;
+
+// 自動車重量税の 0.5t あたりの年額（エコカーは本則税率、13 年、18 年経過で重課）と軽自動車の年額
+const WEIGHT_TAX_PER_HALF_TON: u32 = 4100;
+const ECO_WEIGHT_TAX_PER_HALF_TON: u32 = 2500;
+const OLD_WEIGHT_TAX_PER_HALF_TON: u32 = 5700;
+const VERY_OLD_WEIGHT_TAX_PER_HALF_TON: u32 = 6300;
+const KEI_WEIGHT_TAX: u32 = 3300;
+const ECO_KEI_WEIGHT_TAX: u32 = 2500;
+const OLD_KEI_WEIGHT_TAX: u32 = 4100;
+const VERY_OLD_KEI_WEIGHT_TAX: u32 = 4400;
+const VERY_OLD_CAR_YEARS: u16 = 18;
+
+// 自賠責保険料（本土、新車登録時の 37 か月と車検ごとの 24 か月）
+const LIABILITY_INSURANCE_FEE_37_MONTHS: u32 = 24190;
+const LIABILITY_INSURANCE_FEE_24_MONTHS: u32 = 17650;
+const KEI_LIABILITY_INSURANCE_FEE_37_MONTHS: u32 = 24040;
+const KEI_LIABILITY_INSURANCE_FEE_24_MONTHS: u32 = 17540;
+
+// 車検の間隔
+const FIRST_INSPECTION_YEARS: u16 = 3;
+const INSPECTION_INTERVAL_YEARS: u16 = 2;
+
+// 車検の整備費用、代行手数料、印紙代
+const DEFAULT_INSPECTION_FEE: u32 = 60000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+pub enum VehicleClass {
+    Kei, // 軽自動車
+    Passenger, // 普通自動車、小型自動車
+}
+
+// 車の諸元
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VehicleSpec {
+    pub class: VehicleClass,
+    pub displacement_cc: u32, // 総排気量（電気自動車は 0）
+    pub weight_kg: u32, // 車両重量
+    pub eco_car: bool, // エコカー減税の対象（重量税が本則税率になり、13 年超の重課がない）
+    pub registration_year: u16, // 新車登録の年
+    pub inspection_fee: u32, // 一回の車検の整備費用、代行手数料
+}
+
+impl VehicleSpec {
+    fn age(&self, year: u16) -> u16 {
+        year.saturating_sub(self.registration_year)
+    }
+
+    // 13 年経過の重課の対象（エコカーは重課がない）
+    fn is_old(&self, year: u16) -> bool {
+        !self.eco_car && OLD_CAR_YEARS <= self.age(year)
+    }
+
+    // その年の自動車税（種別割）
+    pub fn estimate_car_type_tax(&self, year: u16) -> u32 {
+        let is_old = self.is_old(year);
+        match self.class {
+            VehicleClass::Kei => if is_old { OLD_KEI_CAR_TYPE_TAX } else { KEI_CAR_TYPE_TAX },
+            VehicleClass::Passenger => {
+                let (_, tax) = CAR_TYPE_TAX_TABLE.iter()
+                    .find(|(limit, _)| self.displacement_cc <= *limit)
+                    .unwrap();
+                if is_old {
+                    (*tax as f64 * OLD_CAR_TYPE_TAX_RATE) as u32 / 100 * 100
+                } else {
+                    *tax
+                }
+            },
+        }
+    }
+
+    // その年に納める重量税の 1 年分
+    fn estimate_annual_weight_tax(&self, year: u16) -> u32 {
+        let age = self.age(year);
+        let (rate, eco_rate, old_rate, very_old_rate, units) = match self.class {
+            VehicleClass::Kei => (KEI_WEIGHT_TAX, ECO_KEI_WEIGHT_TAX, OLD_KEI_WEIGHT_TAX, VERY_OLD_KEI_WEIGHT_TAX, 1),
+            VehicleClass::Passenger => (
+                WEIGHT_TAX_PER_HALF_TON,
+                ECO_WEIGHT_TAX_PER_HALF_TON,
+                OLD_WEIGHT_TAX_PER_HALF_TON,
+                VERY_OLD_WEIGHT_TAX_PER_HALF_TON,
+                self.weight_kg.div_ceil(500),
+            ),
+        };
+        let rate = if self.eco_car {
+            eco_rate
+        } else if VERY_OLD_CAR_YEARS <= age {
+            very_old_rate
+        } else if self.is_old(year) {
+            old_rate
+        } else {
+            rate
+        };
+        rate * units
+    }
+
+    pub fn is_inspection_year(&self, year: u16) -> bool {
+        let age = self.age(year);
+        FIRST_INSPECTION_YEARS <= age && (age - FIRST_INSPECTION_YEARS).is_multiple_of(INSPECTION_INTERVAL_YEARS)
+    }
+
+    // 新車登録の年の重量税（3 年分）と自賠責（37 か月）
+    pub fn estimate_registration_expense(&self) -> u32 {
+        let liability_insurance_fee = match self.class {
+            VehicleClass::Kei => KEI_LIABILITY_INSURANCE_FEE_37_MONTHS,
+            VehicleClass::Passenger => LIABILITY_INSURANCE_FEE_37_MONTHS,
+        };
+        self.estimate_annual_weight_tax(self.registration_year) * FIRST_INSPECTION_YEARS as u32 + liability_insurance_fee
+    }
+
+    // 車検の年の重量税（2 年分）、自賠責（24 か月）、整備費用
+    pub fn estimate_inspection_expense(&self, year: u16) -> u32 {
+        if !self.is_inspection_year(year) {
+            return 0;
+        }
+        let liability_insurance_fee = match self.class {
+            VehicleClass::Kei => KEI_LIABILITY_INSURANCE_FEE_24_MONTHS,
+            VehicleClass::Passenger => LIABILITY_INSURANCE_FEE_24_MONTHS,
+        };
+        self.estimate_annual_weight_tax(year) * INSPECTION_INTERVAL_YEARS as u32 + liability_insurance_fee + self.inspection_fee
+    }
+}
+
+// 諸元から税金、自賠責、車検を求めた Car を作る
+pub struct CarBuilder {
+    car: Car,
+    spec: VehicleSpec,
+}
+
+impl CarBuilder {
+    pub fn new(class: VehicleClass, displacement_cc: u32, weight_kg: u32, registration_year: u16) -> Self {
+        Self {
+            car: Car {
+                start_year: registration_year,
+                end_year: registration_year + FIRST_INSPECTION_YEARS + INSPECTION_INTERVAL_YEARS * 2,
+                annual_car_type_tax: 0,
+                annual_weight_tax: 0,
+                annual_liability_insurance_fee: 0,
+                annual_optional_insurance_fee: 0,
+                annual_inspection_fee: 0,
+                annual_gas_expense: 0,
+                annual_consumables_expense: 0,
+                monthly_parking_fee: 0,
+                down_payment: 0,
+                loan: None,
+                price: None,
+                depreciation: Depreciation::None,
+                spec: None,
+            },
+            spec: VehicleSpec {
+                class,
+                displacement_cc,
+                weight_kg,
+                eco_car: false,
+                registration_year,
+                inspection_fee: DEFAULT_INSPECTION_FEE,
+            },
+        }
+    }
+
+    // 持つ期間（既定は新車登録から 2 回目の車検の前まで）
+    pub fn years(mut self, start_year: u16, end_year: u16) -> Self {
+        self.car.start_year = start_year;
+        self.car.end_year = end_year;
+        self
+    }
+
+    pub fn eco_car(mut self, eco_car: bool) -> Self {
+        self.spec.eco_car = eco_car;
+        self
+    }
+
+    pub fn inspection_fee(mut self, inspection_fee: u32) -> Self {
+        self.spec.inspection_fee = inspection_fee;
+        self
+    }
+
+    pub fn optional_insurance_fee(mut self, annual_optional_insurance_fee: u32) -> Self {
+        self.car.annual_optional_insurance_fee = annual_optional_insurance_fee;
+        self
+    }
+
+    pub fn gas_expense(mut self, annual_gas_expense: u32) -> Self {
+        self.car.annual_gas_expense = annual_gas_expense;
+        self
+    }
+
+    pub fn consumables_expense(mut self, annual_consumables_expense: u32) -> Self {
+        self.car.annual_consumables_expense = annual_consumables_expense;
+        self
+    }
+
+    pub fn parking_fee(mut self, monthly_parking_fee: u32) -> Self {
+        self.car.monthly_parking_fee = monthly_parking_fee;
+        self
+    }
+
+    pub fn down_payment(mut self, down_payment: u32) -> Self {
+        self.car.down_payment = down_payment;
+        self
+    }
+
+    pub fn loan(mut self, loan: YearlyLoan) -> Self {
+        self.car.loan = Some(loan);
+        self
+    }
+
+    pub fn price(mut self, price: u32) -> Self {
+        self.car.price = Some(price);
+        self
+    }
+
+    pub fn depreciation(mut self, depreciation: Depreciation) -> Self {
+        self.car.depreciation = depreciation;
+        self
+    }
+
+    pub fn build(self) -> Car {
+        Car {
+            spec: Some(self.spec),
+            ..self.car
+        }
+    }
+}